@@ -0,0 +1,30 @@
+//! Runtime API of pallet-gov, for checking proofs off-chain without submitting an extrinsic.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::{H256, U256};
+use sp_runtime::RuntimeString;
+use sp_std::vec::Vec;
+
+/// Result of checking a proof. Proofs that fail return the name of the pallet error, since the
+/// message of a `DispatchError` is not encoded.
+pub type ProofResult = Result<(), RuntimeString>;
+
+sp_api::decl_runtime_apis! {
+	pub trait GovApi<BlockNumber> where
+		BlockNumber: Codec,
+	{
+		/// Verify a storage proof of the token contract against the stored storage root of an
+		/// Ethereum block, as the `verify_proof` extrinsic does.
+		fn verify_storage_proof(eth_block_number: BlockNumber, proof: Vec<Vec<u8>>, key: Vec<u8>, value: U256) -> ProofResult;
+		/// Verify a proof that a storage slot of the token contract is not set, as the
+		/// `verify_non_inclusion` extrinsic does.
+		fn verify_storage_absence(eth_block_number: BlockNumber, proof: Vec<Vec<u8>>, key: Vec<u8>) -> ProofResult;
+		/// Verify the values of several storage slots of the token contract with a shared node set, as
+		/// the `verify_multi_proof` extrinsic does.
+		fn verify_storage_multi_proof(eth_block_number: BlockNumber, nodes: Vec<Vec<u8>>, items: Vec<(Vec<u8>, U256)>) -> ProofResult;
+		/// The stored storage root of an Ethereum block.
+		fn storage_root_at(eth_block_number: BlockNumber) -> Option<H256>;
+	}
+}
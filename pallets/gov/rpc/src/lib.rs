@@ -0,0 +1,160 @@
+//! RPC interface of pallet-gov, for checking proofs before paying fees to submit them.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256, U256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_gov_rpc_runtime_api::GovApi as GovRuntimeApi;
+use pallet_gov_rpc_runtime_api::ProofResult;
+
+/// Result of checking a proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofCheck {
+	/// Whether the extrinsic verifying the proof would succeed.
+	pub valid: bool,
+	/// Name of the pallet error that an invalid proof fails with, e.g. `ProofHashMismatch`.
+	pub error: Option<String>,
+}
+
+impl From<ProofResult> for ProofCheck {
+	fn from(result: ProofResult) -> Self {
+		ProofCheck {
+			valid: result.is_ok(),
+			error: result.err().map(|error| error.to_string()),
+		}
+	}
+}
+
+#[rpc]
+pub trait GovApi<BlockHash, BlockNumber> {
+	/// Whether a storage proof of the token contract is valid against the stored storage root of an
+	/// Ethereum block, i.e. whether the `verify_proof` extrinsic would succeed.
+	#[rpc(name = "gov_verifyProof")]
+	fn verify_proof(
+		&self,
+		eth_block_number: BlockNumber,
+		proof: Vec<Bytes>,
+		key: Bytes,
+		value: U256,
+		at: Option<BlockHash>,
+	) -> Result<ProofCheck>;
+
+	/// Whether a proof that a storage slot of the token contract is not set is valid, i.e. whether the
+	/// `verify_non_inclusion` extrinsic would succeed.
+	#[rpc(name = "gov_verifyAbsence")]
+	fn verify_absence(
+		&self,
+		eth_block_number: BlockNumber,
+		proof: Vec<Bytes>,
+		key: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<ProofCheck>;
+
+	/// Whether the values of several storage slots of the token contract are proven by a shared node
+	/// set, i.e. whether the `verify_multi_proof` extrinsic would succeed.
+	#[rpc(name = "gov_verifyMultiProof")]
+	fn verify_multi_proof(
+		&self,
+		eth_block_number: BlockNumber,
+		nodes: Vec<Bytes>,
+		items: Vec<(Bytes, U256)>,
+		at: Option<BlockHash>,
+	) -> Result<ProofCheck>;
+
+	/// The stored storage root of an Ethereum block.
+	#[rpc(name = "gov_storageRoot")]
+	fn storage_root(&self, eth_block_number: BlockNumber, at: Option<BlockHash>) -> Result<Option<H256>>;
+}
+
+/// Implementation of the gov RPC methods.
+pub struct Gov<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Gov<C, B> {
+	/// Create a new instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Gov { client, _marker: Default::default() }
+	}
+}
+
+/// Error code of a failed runtime API call.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(error: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to call the gov runtime API.".into(),
+		data: Some(format!("{:?}", error).into()),
+	}
+}
+
+impl<C, Block, BlockNumber> GovApi<<Block as BlockT>::Hash, BlockNumber> for Gov<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync,
+	C::Api: GovRuntimeApi<Block, BlockNumber>,
+	BlockNumber: Codec,
+{
+	fn verify_proof(
+		&self,
+		eth_block_number: BlockNumber,
+		proof: Vec<Bytes>,
+		key: Bytes,
+		value: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ProofCheck> {
+		let api = self.client.runtime_api();
+		// Default to the best block.
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let proof = proof.into_iter().map(|node| node.0).collect();
+		api.verify_storage_proof(&at, eth_block_number, proof, key.0, value)
+			.map(ProofCheck::from)
+			.map_err(runtime_error)
+	}
+
+	fn verify_absence(
+		&self,
+		eth_block_number: BlockNumber,
+		proof: Vec<Bytes>,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ProofCheck> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let proof = proof.into_iter().map(|node| node.0).collect();
+		api.verify_storage_absence(&at, eth_block_number, proof, key.0)
+			.map(ProofCheck::from)
+			.map_err(runtime_error)
+	}
+
+	fn verify_multi_proof(
+		&self,
+		eth_block_number: BlockNumber,
+		nodes: Vec<Bytes>,
+		items: Vec<(Bytes, U256)>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ProofCheck> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let nodes = nodes.into_iter().map(|node| node.0).collect();
+		let items = items.into_iter().map(|(key, value)| (key.0, value)).collect();
+		api.verify_storage_multi_proof(&at, eth_block_number, nodes, items)
+			.map(ProofCheck::from)
+			.map_err(runtime_error)
+	}
+
+	fn storage_root(&self, eth_block_number: BlockNumber, at: Option<<Block as BlockT>::Hash>) -> Result<Option<H256>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.storage_root_at(&at, eth_block_number).map_err(runtime_error)
+	}
+}
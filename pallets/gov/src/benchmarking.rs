@@ -0,0 +1,428 @@
+//! Benchmarks of pallet-gov. Proof verification is parameterised by the number of proof nodes and the total
+//! proof size in bytes.
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use rlp::RlpStream;
+use eth_trie_proof::{uint_key, Account};
+
+const SEED: u32 = 0;
+// Length of an RLP encoded hash reference.
+const REFERENCE_LENGTH: u32 = 33;
+// Bytes that a `remark` call adds to the remarked bytes when encoded, at most.
+const REMARK_OVERHEAD: u32 = 8;
+
+// Build a proof of the RLP encoded `value` at the hashed `key`, made of `nodes - 1` branch nodes above
+// a leaf node, of about `size` bytes in total. Besides the child on the path of the key, the branch nodes
+// are padded with references to sibling nodes, which verification hashes but never follows, up to 15 per
+// branch node. Returns the root and the proof.
+fn build_proof(key: &[u8; 32], value: Vec<u8>, nodes: u32, size: u32) -> (H256, Vec<Vec<u8>>) {
+	let filler = size.saturating_sub(proof_size(&build_path(key, value.clone(), 0, nodes, 0)));
+	let proof = build_path(key, value, 0, nodes, filler);
+	(H256(keccak(&proof[0])), proof)
+}
+
+// Build a node set proving the RLP encoded `value` at each of `keys`, whose hashes must start with different
+// nibbles, of `nodes` nodes in total. The paths of the keys only share the root node, and are padded as
+// `build_proof` does, to about `size` bytes in total.
+fn build_multi_proof(keys: &[H256], value: Vec<u8>, nodes: u32, size: u32) -> Vec<Vec<u8>> {
+	let build = |filler: u32| {
+		let k = keys.len() as u32;
+		let mut children = Vec::new();
+		children.resize(16, Vec::new());
+		let mut proof = Vec::new();
+		for (index, key) in keys.iter().enumerate() {
+			let hashed_key = keccak(key.as_bytes());
+			let path_nodes = (nodes - 1) / k + if (index as u32) < (nodes - 1) % k { 1 } else { 0 };
+			let path = build_path(&hashed_key, value.clone(), 1, path_nodes, filler / k);
+			children[usize::from(hashed_key[0] >> 4)] = keccak(&path[0]).to_vec();
+			proof.extend(path);
+		}
+		let mut root = RlpStream::new_list(17);
+		for child in &children {
+			root.append(child);
+		}
+		root.append_empty_data();
+		proof.insert(0, root.out().to_vec());
+		proof
+	};
+	build(size.saturating_sub(proof_size(&build(0))))
+}
+
+// Build the nodes of a proof as `build_proof` does, below a parent node that consumed the first `start`
+// key nibbles.
+fn build_path(key: &[u8; 32], value: Vec<u8>, start: usize, nodes: u32, filler: u32) -> Vec<Vec<u8>> {
+	let nibble = |index: usize| if index % 2 == 0 { key[index / 2] >> 4 } else { key[index / 2] & 0x0f };
+	let branches = nodes as usize - 1;
+
+	// Compact encoded path of the key nibbles left for the leaf node.
+	let mut path = Vec::new();
+	let mut index = start + branches;
+	if (64 - index) % 2 == 1 {
+		path.push(0x30 | nibble(index));
+		index += 1;
+	} else {
+		path.push(0x20);
+	}
+	while index < 64 {
+		path.push(nibble(index) << 4 | nibble(index + 1));
+		index += 2;
+	}
+	let mut leaf = RlpStream::new_list(2);
+	leaf.append(&path).append(&value);
+
+	let mut proof = Vec::new();
+	proof.push(leaf.out().to_vec());
+	let sibling = H256::repeat_byte(0xff);
+	let references = (filler / REFERENCE_LENGTH) as usize;
+	for level in (0..branches).rev() {
+		let depth = start + level;
+		let child = keccak(&proof[proof.len() - 1]).to_vec();
+		let siblings = references / branches + if level < references % branches { 1 } else { 0 };
+		let mut branch = RlpStream::new_list(17);
+		for position in 0..16u8 {
+			// Siblings follow the child on the path of the key.
+			let offset = (position + 16 - nibble(depth)) % 16;
+			if offset == 0 {
+				branch.append(&child);
+			} else if usize::from(offset) <= siblings {
+				branch.append(&sibling);
+			} else {
+				branch.append_empty_data();
+			}
+		}
+		branch.append_empty_data();
+		proof.push(branch.out().to_vec());
+	}
+	proof.reverse();
+	proof
+}
+
+// A `personal_sign` signature, which recovers to some Ethereum address for any message.
+const SIGNATURE: [u8; 65] = [
+	0xf7, 0x51, 0x04, 0x61, 0xee, 0xbf, 0x8e, 0x5d, 0xbe, 0x12, 0x3b, 0x21, 0x1d,
+	0x71, 0xd0, 0x2c, 0x4c, 0xce, 0x36, 0x6b, 0x27, 0x64, 0x71, 0xe1, 0x68, 0xbb,
+	0x19, 0x60, 0x43, 0xce, 0xca, 0xc7, 0x47, 0x1d, 0x8e, 0x8d, 0x03, 0x87, 0x36,
+	0x63, 0xf9, 0x9d, 0x2c, 0x41, 0x45, 0x57, 0x5c, 0xd4, 0xb0, 0x32, 0xda, 0x85,
+	0x71, 0x1e, 0xd3, 0xaf, 0xeb, 0x24, 0xa4, 0x27, 0x3b, 0xb6, 0x94, 0xc6, 0x1b,
+];
+
+// A London block header with the longest extra data.
+fn rlp_header() -> Vec<u8> {
+	let mut stream = RlpStream::new_list(16);
+	stream.append(&H256::repeat_byte(0x01)) // parent hash
+		.append(&H256::repeat_byte(0x02)) // ommers hash
+		.append(&H160::repeat_byte(0x03)) // beneficiary
+		.append(&H256::repeat_byte(0x04)) // state root
+		.append(&H256::repeat_byte(0x05)) // transactions root
+		.append(&H256::repeat_byte(0x06)) // receipts root
+		.append(&vec![0u8; 256]) // logs bloom
+		.append(&U256::from(9_000_000_000_000_000u64)) // difficulty
+		.append(&1u64) // number
+		.append(&30_000_000u64) // gas limit
+		.append(&15_000_000u64) // gas used
+		.append(&1_630_000_000u64) // timestamp
+		.append(&vec![0u8; 32]) // extra data
+		.append(&H256::repeat_byte(0x07)) // mix hash
+		.append(&vec![0u8; 8]) // nonce
+		.append(&50_000_000_000u64); // base fee
+	stream.out().to_vec()
+}
+
+// Fill the tracked storage root blocks up to `T::MaxStoredRoots`, after the Ethereum block numbers
+// used by the benchmarks, so that storing a root shifts all of them.
+fn fill_storage_root_blocks<T: Config>() {
+	let blocks = (0..T::MaxStoredRoots::get()).map(|n| T::BlockNumber::from(n + 2)).collect::<Vec<_>>();
+	<StorageRootBlocks<T>>::put(blocks);
+}
+
+// Fill the tracked attested blocks up to `T::MaxStoredRoots`, from the Ethereum block number 1 used by the
+// benchmarks, so that storing its root queues its attestations for removal.
+fn fill_attested_blocks<T: Config>() {
+	let blocks = (0..T::MaxStoredRoots::get()).map(|n| T::BlockNumber::from(n + 1)).collect::<Vec<_>>();
+	<AttestedBlocks<T>>::put(blocks);
+}
+
+// A proposal of `proposer` to remark the longest bytes, which voting and closing read and write back.
+fn longest_proposal<T: Config>(
+	proposer: T::AccountId,
+	deposit: BalanceOf<T>,
+	snapshot: T::BlockNumber) -> ProposalInfo<T::Proposal, T::AccountId, T::BlockNumber, BalanceOf<T>>
+{
+	let remark = vec![0; (T::MaxProposalLength::get() - REMARK_OVERHEAD) as usize];
+	ProposalInfo {
+		proposer,
+		deposit,
+		proposal: frame_system::Call::<T>::remark(remark).into(),
+		snapshot,
+		end: T::BlockNumber::from(10u32),
+		ayes: U256::zero(),
+		nays: U256::zero(),
+	}
+}
+
+benchmarks! {
+	store_storage_root {
+		fill_storage_root_blocks::<T>();
+		fill_attested_blocks::<T>();
+		let origin = T::SubmitOrigin::successful_origin();
+		let eth_block_number = T::BlockNumber::from(1u32);
+		let storage_root = H256::repeat_byte(1);
+	}: {
+		<Module<T>>::store_storage_root(origin, eth_block_number, storage_root)?;
+	}
+	verify {
+		assert_eq!(<StorageRoots<T>>::get(eth_block_number), Some(storage_root));
+	}
+
+	correct_storage_root {
+		let eth_block_number = T::BlockNumber::from(1u32);
+		<StorageRoots<T>>::insert(eth_block_number, H256::repeat_byte(1));
+		let storage_root = H256::repeat_byte(2);
+	}: _(RawOrigin::Root, eth_block_number, storage_root)
+	verify {
+		assert_eq!(<StorageRoots<T>>::get(eth_block_number), Some(storage_root));
+	}
+
+	remove_storage_root {
+		fill_storage_root_blocks::<T>();
+		let eth_block_number = T::BlockNumber::from(2u32);
+		<StorageRoots<T>>::insert(eth_block_number, H256::repeat_byte(1));
+	}: _(RawOrigin::Root, eth_block_number)
+	verify {
+		assert!(!<StorageRoots<T>>::contains_key(eth_block_number));
+	}
+
+	set_relayers {
+		let r in 1 .. T::MaxRelayers::get();
+		// The pending attestations of the replaced set are queued for removal.
+		fill_attested_blocks::<T>();
+		let relayers: Vec<T::AccountId> = (0..r).map(|i| account("relayer", i, SEED)).collect();
+		let origin = T::SubmitOrigin::successful_origin();
+	}: {
+		<Module<T>>::set_relayers(origin, relayers, r)?;
+	}
+	verify {
+		assert_eq!(<Module<T>>::relayers().len(), r as usize);
+	}
+
+	attest_storage_root {
+		// Relayers that attested before the caller: one attested the root that the caller's attestation
+		// finalizes, the others each attested a different root. All of them are read.
+		let r in 1 .. T::MaxRelayers::get();
+		fill_storage_root_blocks::<T>();
+		fill_attested_blocks::<T>();
+		let relayers: Vec<T::AccountId> = (0..r).map(|i| account("relayer", i, SEED)).collect();
+		<Module<T>>::set_relayers(T::SubmitOrigin::successful_origin(), relayers.clone(), r.min(2))?;
+		let eth_block_number = T::BlockNumber::from(1u32);
+		let storage_root = H256::repeat_byte(1);
+		for (index, relayer) in relayers.iter().take(r as usize - 1).enumerate() {
+			let attested_root = H256::repeat_byte(index as u8 + 1);
+			<Module<T>>::attest_storage_root(RawOrigin::Signed(relayer.clone()).into(), eth_block_number, attested_root)?;
+		}
+		let caller = relayers[r as usize - 1].clone();
+	}: _(RawOrigin::Signed(caller), eth_block_number, storage_root)
+	verify {
+		assert_eq!(<StorageRoots<T>>::get(eth_block_number), Some(storage_root));
+	}
+
+	submit_header {
+		let origin = T::SubmitOrigin::successful_origin();
+		let header = rlp_header();
+		let block_hash = H256(keccak(&header));
+	}: {
+		<Module<T>>::submit_header(origin, block_hash, header)?;
+	}
+	verify {
+		assert!(Headers::contains_key(block_hash));
+	}
+
+	prove_storage_root {
+		let n in 2 .. T::MaxProofNodes::get();
+		// Sizes below the minimal proof of `n` nodes, or beyond what they can hold, are rounded to them.
+		let b in 0 .. T::MaxProofNodes::get().saturating_mul(T::MaxNodeLength::get());
+		fill_storage_root_blocks::<T>();
+		fill_attested_blocks::<T>();
+		let account = Account {
+			nonce: U256::one(),
+			balance: U256::zero(),
+			storage_root: H256::repeat_byte(1),
+			code_hash: H256::repeat_byte(2),
+		};
+		let key = keccak(T::TokenContract::get().as_bytes());
+		let (state_root, proof) = build_proof(&key, rlp::encode(&account).to_vec(), n, b);
+		let eth_block_number = T::BlockNumber::from(1u32);
+		let block_hash = H256::repeat_byte(3);
+		Headers::insert(block_hash, EthHeader {
+			parent_hash: H256::zero(),
+			state_root,
+			transactions_root: H256::zero(),
+			receipts_root: H256::zero(),
+			number: 1,
+			timestamp: 0,
+		});
+		<HeaderHashes<T>>::insert(eth_block_number, block_hash);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), eth_block_number, proof)
+	verify {
+		assert_eq!(<StorageRoots<T>>::get(eth_block_number), Some(account.storage_root));
+	}
+
+	verify_proof {
+		let n in 2 .. T::MaxProofNodes::get();
+		let b in 0 .. T::MaxProofNodes::get().saturating_mul(T::MaxNodeLength::get());
+		let key = H256::repeat_byte(0x42);
+		let value = U256::exp10(18);
+		let (storage_root, proof) = build_proof(&keccak(key.as_bytes()), rlp::encode(&value).to_vec(), n, b);
+		let eth_block_number = T::BlockNumber::from(1u32);
+		<StorageRoots<T>>::insert(eth_block_number, storage_root);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), eth_block_number, proof, key.as_bytes().to_vec(), value)
+
+	verify_multi_proof {
+		// Keys whose paths only share the root node, which is the most expensive node set for `k` keys.
+		let k in 1 .. T::MaxProofKeys::get().min(16);
+		// Paths of at most 63 nodes below the root node fit the 64 nibbles of a key.
+		let n in 17 .. T::MaxProofNodes::get().saturating_mul(T::MaxProofKeys::get()).min(64);
+		let b in 0 .. T::MaxProofNodes::get().saturating_mul(T::MaxProofKeys::get()).min(64) * T::MaxNodeLength::get();
+		let mut keys = Vec::new();
+		let mut nibbles = [false; 16];
+		let mut candidate = 0u32;
+		while keys.len() < k as usize {
+			let key = uint_key(candidate.into());
+			let nibble = usize::from(keccak(key.as_bytes())[0] >> 4);
+			if !nibbles[nibble] {
+				nibbles[nibble] = true;
+				keys.push(key);
+			}
+			candidate += 1;
+		}
+		let value = U256::exp10(18);
+		let nodes = build_multi_proof(&keys, rlp::encode(&value).to_vec(), n, b);
+
+		let eth_block_number = T::BlockNumber::from(1u32);
+		<StorageRoots<T>>::insert(eth_block_number, H256(keccak(&nodes[0])));
+		let items = keys.iter().map(|key| (key.as_bytes().to_vec(), value)).collect::<Vec<_>>();
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), eth_block_number, nodes, items)
+
+	claim_eth_address {
+		let caller: T::AccountId = whitelisted_caller();
+		let signature = ecdsa::Signature::from_raw(SIGNATURE);
+	}: _(RawOrigin::Signed(caller.clone()), signature)
+	verify {
+		assert!(<EthAddresses<T>>::contains_key(&caller));
+	}
+
+	register_votes {
+		let n in 2 .. T::MaxProofNodes::get();
+		let b in 0 .. T::MaxProofNodes::get().saturating_mul(T::MaxNodeLength::get());
+		let caller: T::AccountId = whitelisted_caller();
+		let address = H160::repeat_byte(0x17);
+		<EthAddresses<T>>::insert(&caller, address);
+		let key = <Module<T>>::balance_key(&address);
+		let value = U256::exp10(18);
+		let (storage_root, proof) = build_proof(&keccak(key.as_bytes()), rlp::encode(&value).to_vec(), n, b);
+		let snapshot = T::BlockNumber::from(1u32);
+		<StorageRoots<T>>::insert(snapshot, storage_root);
+	}: _(RawOrigin::Signed(caller.clone()), snapshot, proof)
+	verify {
+		assert_eq!(<VoteWeights<T>>::get(&caller, snapshot), Some((value, storage_root)));
+	}
+
+	propose {
+		// Length of the remarked bytes of the proposal.
+		let l in 0 .. T::MaxProposalLength::get() - REMARK_OVERHEAD;
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::ProposalDeposit::get() * 10u32.into());
+		let snapshot = T::BlockNumber::from(1u32);
+		<StorageRoots<T>>::insert(snapshot, H256::repeat_byte(1));
+		// The proposals already ending at the same block are read and written back.
+		let end = <frame_system::Module<T>>::block_number() + T::VotingPeriod::get();
+		<ProposalsEnding<T>>::insert(end, (1..T::MaxProposals::get()).collect::<Vec<_>>());
+		let proposal: T::Proposal = frame_system::Call::<T>::remark(vec![0; l as usize]).into();
+	}: _(RawOrigin::Signed(caller), Box::new(proposal), snapshot)
+	verify {
+		assert!(<Proposals<T>>::contains_key(0));
+	}
+
+	vote {
+		// Voting reads and writes back the proposal, which is at most `MaxProposalLength` bytes long.
+		let caller: T::AccountId = whitelisted_caller();
+		let snapshot = T::BlockNumber::from(1u32);
+		let storage_root = H256::repeat_byte(1);
+		<StorageRoots<T>>::insert(snapshot, storage_root);
+		<VoteWeights<T>>::insert(&caller, snapshot, (U256::exp10(18), storage_root));
+		<Proposals<T>>::insert(0, longest_proposal::<T>(caller.clone(), Zero::zero(), snapshot));
+	}: _(RawOrigin::Signed(caller.clone()), 0, true)
+	verify {
+		assert_eq!(<Votes<T>>::get(0, &caller), Some(true));
+	}
+
+	on_initialize {
+		// Storage roots pruned beyond `MaxStoredRoots`, which are the most expensive removals.
+		let d in 0 .. T::MaxRemovals::get();
+		let stored = T::MaxStoredRoots::get() + d;
+		let blocks = (0..stored).map(|n| T::BlockNumber::from(n + 1)).collect::<Vec<_>>();
+		for eth_block_number in &blocks {
+			<StorageRoots<T>>::insert(eth_block_number, H256::repeat_byte(1));
+		}
+		<StorageRootBlocks<T>>::put(blocks);
+		let n = <frame_system::Module<T>>::block_number() + T::BlockNumber::from(1u32);
+	}: {
+		<Module<T> as OnInitialize<T::BlockNumber>>::on_initialize(n);
+	}
+	verify {
+		assert_eq!(<Module<T>>::storage_root_blocks().len(), T::MaxStoredRoots::get() as usize);
+	}
+
+	close_proposal {
+		// A rejected proposal whose deposit is returned, and whose snapshot root pruning kept for it.
+		let caller: T::AccountId = whitelisted_caller();
+		let deposit = T::ProposalDeposit::get();
+		T::Currency::make_free_balance_be(&caller, deposit * 10u32.into());
+		T::Currency::reserve(&caller, deposit)?;
+		fill_storage_root_blocks::<T>();
+		let snapshot = T::BlockNumber::from(1u32);
+		<StorageRoots<T>>::insert(snapshot, H256::repeat_byte(1));
+		<SnapshotProposals<T>>::insert(snapshot, 1);
+		<Proposals<T>>::insert(0, longest_proposal::<T>(caller, deposit, snapshot));
+	}: {
+		<Module<T>>::close_proposal(0);
+	}
+	verify {
+		assert!(!<Proposals<T>>::contains_key(0));
+		assert!(!<StorageRoots<T>>::contains_key(snapshot));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_store_storage_root::<Test>());
+			assert_ok!(test_benchmark_prove_storage_root::<Test>());
+			assert_ok!(test_benchmark_verify_proof::<Test>());
+			assert_ok!(test_benchmark_verify_multi_proof::<Test>());
+			assert_ok!(test_benchmark_register_votes::<Test>());
+			assert_ok!(test_benchmark_claim_eth_address::<Test>());
+			assert_ok!(test_benchmark_set_relayers::<Test>());
+			assert_ok!(test_benchmark_attest_storage_root::<Test>());
+			assert_ok!(test_benchmark_submit_header::<Test>());
+			assert_ok!(test_benchmark_propose::<Test>());
+			assert_ok!(test_benchmark_vote::<Test>());
+			assert_ok!(test_benchmark_correct_storage_root::<Test>());
+			assert_ok!(test_benchmark_remove_storage_root::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+			assert_ok!(test_benchmark_close_proposal::<Test>());
+		});
+	}
+}
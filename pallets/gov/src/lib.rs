@@ -3,16 +3,23 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch::{DispatchResult}, traits::Get};
-use frame_system::ensure_signed;
+use codec::{Decode, Encode};
+use core::convert::TryFrom;
+use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch::{DispatchResult}, ensure,
+	storage::IterableStorageDoubleMap, traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+	weights::{GetDispatchInfo, Weight}, Parameter};
+use frame_system::{ensure_root, ensure_signed};
 use sp_core::{
-	H256,
+	ecdsa, H160, H256, U256,
+};
+use sp_runtime::{traits::{Dispatchable, Zero}, PerThing, Perbill, RuntimeDebug};
+use eth_trie_proof::{
+	address_mapping_slot, keccak, read_storage_value, slot, verify_absence, verify_account_proof, verify_multi_proof,
+	verify_storage_value, Header, ProofError,
 };
 
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
-
-mod verifier;
+use alloc::{boxed::Box, vec::Vec};
 
 #[cfg(test)]
 mod mock;
@@ -20,14 +27,130 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+pub use weights::WeightInfo;
+
 // Storage root stored as bytes.
 // TODO: Explore making this sp_core::Bytes or Vec<u8>
 pub type StorageRoot = H256;
 
+// Tag at the start of the message signed to claim an Ethereum address. The genesis hash of the chain
+// follows it, so that a signature of the same account for another chain or application cannot be replayed.
+const CLAIM_MESSAGE_TAG: &[u8] = b"pallet-gov claim_eth_address:";
+
+/// The fields of an Ethereum block header kept by the pallet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct EthHeader {
+	pub parent_hash: H256,
+	/// Root of the state trie, which account proofs are verified against.
+	pub state_root: H256,
+	pub transactions_root: H256,
+	pub receipts_root: H256,
+	pub number: u64,
+	pub timestamp: u64,
+}
+
+impl From<Header> for EthHeader {
+	fn from(header: Header) -> Self {
+		EthHeader {
+			parent_hash: header.parent_hash,
+			state_root: header.state_root,
+			transactions_root: header.transactions_root,
+			receipts_root: header.receipts_root,
+			number: header.number,
+			timestamp: header.timestamp,
+		}
+	}
+}
+
+/// Index of a governance proposal.
+pub type ProposalIndex = u32;
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A governance proposal, voted on with the vote weights registered at an Ethereum snapshot block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ProposalInfo<Proposal, AccountId, BlockNumber, Balance> {
+	pub proposer: AccountId,
+	/// Amount reserved from the proposer until voting ends.
+	pub deposit: Balance,
+	/// The call dispatched with the root origin if the proposal passes.
+	pub proposal: Proposal,
+	/// Ethereum block number whose registered vote weights are used for voting.
+	pub snapshot: BlockNumber,
+	/// Block number at which voting ends and the votes are tallied.
+	pub end: BlockNumber,
+	/// Total vote weight in favour of the proposal.
+	pub ayes: U256,
+	/// Total vote weight against the proposal.
+	pub nays: U256,
+}
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Config: frame_system::Config {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+	/// Address of the Ethereum token contract whose storage roots can be proven with account proofs.
+	type TokenContract: Get<H160>;
+	/// Storage slot of the `mapping(address => uint)` holding token balances in the token contract.
+	type BalanceSlot: Get<u64>;
+	/// The call wrapped by governance proposals. Benchmarks propose `frame_system` remarks.
+	type Proposal: Parameter + Dispatchable<Origin = Self::Origin> + GetDispatchInfo + From<frame_system::Call<Self>>;
+	/// Currency in which proposal deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Amount reserved from a proposer until voting on the proposal ends.
+	type ProposalDeposit: Get<BalanceOf<Self>>;
+	/// Maximum number of proposals whose voting ends at the same block, which are all tallied and
+	/// dispatched in `on_initialize`.
+	type MaxProposals: Get<u32>;
+	/// Maximum dispatch weight of a proposal. `MaxProposals` proposals of this weight must fit in a block.
+	type MaxProposalWeight: Get<Weight>;
+	/// Maximum length of an encoded proposal in bytes. Voting reads and writes proposals of up to this length.
+	type MaxProposalLength: Get<u32>;
+	/// Number of blocks that a proposal can be voted on. Must be at least one.
+	type VotingPeriod: Get<Self::BlockNumber>;
+	/// Minimum total vote weight of the ayes and nays for a proposal to pass.
+	type Quorum: Get<U256>;
+	/// Fraction of the vote weight that ayes must exceed for a proposal to pass.
+	type Threshold: Get<Perbill>;
+	/// Maximum number of stored storage roots. The roots of the oldest Ethereum blocks beyond it are
+	/// pruned at the start of each block, except those used as snapshots by open proposals, which are
+	/// removed once the proposals close.
+	type MaxStoredRoots: Get<u32>;
+	/// Maximum number of storage roots, votes and attestations removed at the start of a block. Roots beyond
+	/// `MaxStoredRoots`, the votes on closed proposals and stale attestations are removed over several blocks.
+	type MaxRemovals: Get<u32>;
+	/// Maximum number of nodes in a proof.
+	///
+	/// frame-support 3.0 has no `BoundedVec`, so extrinsics take proofs and keys as `Vec`s, which are only
+	/// bounded by the block length when decoded. This bound and the ones below are checked before a proof
+	/// is verified.
+	type MaxProofNodes: Get<u32>;
+	/// Maximum length of a proof node in bytes. In state and storage tries, branch nodes referencing all of
+	/// their children by hash are the longest, at 532 bytes.
+	type MaxNodeLength: Get<u32>;
+	/// Maximum length of a storage key passed to `verify_proof` in bytes.
+	type MaxKeyLength: Get<u32>;
+	/// Maximum number of storage keys verified by `verify_multi_proof`. Its node set may have up to
+	/// `MaxProofNodes` nodes per key it could verify, i.e. `MaxProofNodes * MaxProofKeys` nodes.
+	type MaxProofKeys: Get<u32>;
+	/// Maximum number of relayers. Bounds the storage roots attested for an Ethereum block, which
+	/// `attest_storage_root` iterates over.
+	type MaxRelayers: Get<u32>;
+	/// Origin allowed to store storage roots and block headers, which proofs are verified against,
+	/// and to manage the relayer set.
+	type SubmitOrigin: EnsureOrigin<Self::Origin>;
+	/// Weight information for the extrinsics of this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+// Total length of the proof nodes in bytes, which the cost of verifying a proof grows with.
+fn proof_size(proof: &[Vec<u8>]) -> u32 {
+	proof.iter().fold(0u32, |size, node| size.saturating_add(node.len() as u32))
 }
 
 // The pallet's runtime storage items.
@@ -39,18 +162,99 @@ decl_storage! {
 	trait Store for Module<T: Config> as GovModule {
 		// Learn more about declaring storage items:
 		// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-		pub StorageRoots get(fn storage_root): map hasher(blake2_128_concat) T::BlockNumber => StorageRoot;
+		pub StorageRoots get(fn storage_root): map hasher(blake2_128_concat) T::BlockNumber => Option<StorageRoot>;
+		/// Ethereum block numbers of the stored storage roots that are not pruned yet, sorted.
+		pub StorageRootBlocks get(fn storage_root_blocks): Vec<T::BlockNumber>;
+		/// Ethereum block headers by block hash.
+		pub Headers get(fn header): map hasher(blake2_128_concat) H256 => Option<EthHeader>;
+		/// Hashes of the stored Ethereum block headers by block number.
+		pub HeaderHashes get(fn header_hash): map hasher(blake2_128_concat) T::BlockNumber => Option<H256>;
+		/// Accounts attesting storage roots, sorted.
+		pub Relayers get(fn relayers): Vec<T::AccountId>;
+		/// Number of relayers that must attest the same storage root before it is stored.
+		pub RelayerThreshold get(fn relayer_threshold): u32;
+		/// Index of the current relayer set, incremented by `set_relayers`.
+		pub RelayerSetIndex get(fn relayer_set_index): u32;
+		/// Relayers that attested a storage root of an Ethereum block which is not stored yet, by relayer set
+		/// index and block number. Attestations of earlier relayer sets are ignored, and removed over the
+		/// next blocks like those of blocks whose root is stored.
+		pub Attestations get(fn attestations):
+			double_map hasher(blake2_128_concat) (u32, T::BlockNumber), hasher(blake2_128_concat) StorageRoot
+			=> Vec<T::AccountId>;
+		/// Ethereum block numbers with pending attestations of the current relayer set, sorted. Beyond
+		/// `MaxStoredRoots` of them, the attestations of the oldest Ethereum blocks are removed.
+		pub AttestedBlocks get(fn attested_blocks): Vec<T::BlockNumber>;
+		/// Relayer set indices and Ethereum block numbers of the attestations to remove, oldest first.
+		pub StaleAttestations get(fn stale_attestations): Vec<(u32, T::BlockNumber)>;
+		/// Ethereum address claimed by an account.
+		pub EthAddresses get(fn eth_address): map hasher(blake2_128_concat) T::AccountId => Option<H160>;
+		/// Account that claimed an Ethereum address.
+		pub EthAccounts get(fn eth_account): map hasher(blake2_128_concat) H160 => Option<T::AccountId>;
+		/// Vote weight of an account at an Ethereum snapshot block, proven from the token contract storage,
+		/// and the storage root it was proven against. It is ignored once that root is corrected.
+		pub VoteWeights get(fn vote_weight):
+			double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::BlockNumber
+			=> Option<(U256, StorageRoot)>;
+		/// Number of proposals made, which is the index of the next proposal.
+		pub ProposalCount get(fn proposal_count): ProposalIndex;
+		/// Proposals that are being voted on.
+		pub Proposals get(fn proposal):
+			map hasher(blake2_128_concat) ProposalIndex =>
+				Option<ProposalInfo<T::Proposal, T::AccountId, T::BlockNumber, BalanceOf<T>>>;
+		/// Number of open proposals using the storage root of an Ethereum block as snapshot. Pruning keeps
+		/// these roots until the proposals close.
+		pub SnapshotProposals get(fn snapshot_proposals): map hasher(blake2_128_concat) T::BlockNumber => u32;
+		/// Indices of the proposals whose voting ends at a block.
+		pub ProposalsEnding get(fn proposals_ending): map hasher(blake2_128_concat) T::BlockNumber => Vec<ProposalIndex>;
+		/// Votes on the proposals that are being voted on, or closed but whose votes are not removed yet,
+		/// true for aye.
+		pub Votes get(fn vote_of):
+			double_map hasher(blake2_128_concat) ProposalIndex, hasher(blake2_128_concat) T::AccountId => Option<bool>;
+		/// Indices of the closed proposals whose votes are not removed yet, oldest first.
+		pub ClosedProposals get(fn closed_proposals): Vec<ProposalIndex>;
 	}
 }
 
 // Pallets use events to inform users when important changes are made.
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
-	pub enum Event<T> where BlockNumber = <T as frame_system::Config>::BlockNumber {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+	{
 		/// Storage root stored.
 		StorageRootStored(BlockNumber, StorageRoot),
-		/// True or false for proof result.
+		/// Relayer set replaced. [relayers, threshold]
+		RelayersSet(Vec<AccountId>, u32),
+		/// A relayer attested a storage root. [relayer, block number, storage root]
+		StorageRootAttested(AccountId, BlockNumber, StorageRoot),
+		/// A relayer attested a storage root that differs from a root already attested for the block.
+		/// [relayer, block number, storage root, conflicting storage root]
+		AttestationConflict(AccountId, BlockNumber, StorageRoot, StorageRoot),
+		/// Enough relayers attested a storage root, which is now stored. [block number, storage root]
+		StorageRootFinalized(BlockNumber, StorageRoot),
+		/// Storage root removed or pruned. [block number]
+		StorageRootRemoved(BlockNumber),
+		/// Storage root corrected. [block number, old storage root, new storage root]
+		StorageRootCorrected(BlockNumber, StorageRoot, StorageRoot),
+		/// Ethereum block header stored. [block number, block hash]
+		HeaderStored(BlockNumber, H256),
+		/// True for a successfully verified proof. Failed proofs return an error instead.
 		VerifyProof(bool),
+		/// Ethereum address claimed. [account, Ethereum address]
+		EthAddressClaimed(AccountId, H160),
+		/// Vote weight registered. [account, snapshot block number, weight]
+		VotesRegistered(AccountId, BlockNumber, U256),
+		/// Proposal made. [proposal index, proposer, snapshot block number]
+		Proposed(ProposalIndex, AccountId, BlockNumber),
+		/// Vote cast. [account, proposal index, aye, weight]
+		Voted(AccountId, ProposalIndex, bool, U256),
+		/// Proposal passed and is dispatched. [proposal index]
+		ProposalPassed(ProposalIndex),
+		/// Proposal did not reach the quorum or the threshold. [proposal index]
+		ProposalRejected(ProposalIndex),
+		/// Passed proposal dispatched. [proposal index, dispatch succeeded]
+		ProposalExecuted(ProposalIndex, bool),
 	}
 );
 
@@ -59,6 +263,360 @@ decl_error! {
 	pub enum Error for Module<T: Config> {
 		/// Error names should be descriptive.
 		NoneValue,
+		/// The first proof node does not hash to the stored storage root, e.g. because the root is stale.
+		StorageRootMismatch,
+		/// A proof node does not hash to the reference stored in its parent node.
+		ProofHashMismatch,
+		/// A proof node is not a valid RLP encoded trie node.
+		InvalidProofNode,
+		/// The path stored in a proof node diverges from the storage key.
+		ProofKeyMismatch,
+		/// The value proven for the storage key does not match the given value.
+		ProofValueMismatch,
+		/// A leaf or extension node has an unknown compact path prefix.
+		InvalidPathPrefix,
+		/// The proof ended before reaching the node holding the storage key.
+		IncompleteProof,
+		/// The proof shows that the storage key is set, instead of its absence.
+		ProofKeyPresent,
+		/// The proof has nodes after the node where verification ended.
+		UnusedProofNodes,
+		/// The proof has more than `MaxProofNodes` nodes.
+		TooManyProofNodes,
+		/// A proof node is longer than `MaxNodeLength` bytes.
+		ProofNodeTooLong,
+		/// The storage key is longer than `MaxKeyLength` bytes.
+		KeyTooLong,
+		/// More than `MaxProofKeys` storage keys are verified at once.
+		TooManyProofKeys,
+		/// The first account proof node does not hash to the state root of the block header.
+		StateRootMismatch,
+		/// The block header is not a valid RLP encoded Ethereum block header.
+		InvalidHeader,
+		/// The keccak hash of the block header does not match the given block hash.
+		HeaderHashMismatch,
+		/// A storage root is already stored for the block number. Use `correct_storage_root` to replace it.
+		StorageRootAlreadyStored,
+		/// No storage root is stored for the block number.
+		UnknownStorageRoot,
+		/// A header is already stored for the block number.
+		HeaderAlreadyStored,
+		/// No header is stored for the block number.
+		UnknownHeader,
+		/// The account proof shows that the token contract does not exist at the block.
+		AccountNotFound,
+		/// The signer is not in the relayer set.
+		NotRelayer,
+		/// The relayer already attested a storage root for the block.
+		AlreadyAttested,
+		/// The relayer threshold must be between one and the number of relayers.
+		InvalidThreshold,
+		/// More than `MaxRelayers` relayers.
+		TooManyRelayers,
+		/// No Ethereum address can be recovered from the signature.
+		InvalidSignature,
+		/// The Ethereum address is already claimed by an account.
+		EthAddressAlreadyClaimed,
+		/// The account already claimed an Ethereum address.
+		AccountAlreadyClaimed,
+		/// The account has not claimed an Ethereum address.
+		NoEthAddress,
+		/// The proven token balance is zero.
+		NoVoteWeight,
+		/// The account already registered its vote weight at the snapshot block.
+		VotesAlreadyRegistered,
+		/// The account has not registered its vote weight at the snapshot block of the proposal, or
+		/// registered it against a storage root that was corrected since.
+		VotesNotRegistered,
+		/// No proposal is being voted on with the index.
+		UnknownProposal,
+		/// `MaxProposals` proposals already end at the block.
+		TooManyProposals,
+		/// The dispatch weight of the proposal exceeds `MaxProposalWeight`.
+		ProposalTooHeavy,
+		/// The encoded proposal is longer than `MaxProposalLength` bytes.
+		ProposalTooLong,
+		/// The proposer cannot reserve the proposal deposit.
+		InsufficientDeposit,
+		/// The account already voted on the proposal.
+		AlreadyVoted,
+	}
+}
+
+impl<T: Config> From<ProofError> for Error<T> {
+	fn from(error: ProofError) -> Self {
+		match error {
+			ProofError::HashMismatch(0) => Error::<T>::StorageRootMismatch,
+			ProofError::HashMismatch(_) => Error::<T>::ProofHashMismatch,
+			ProofError::InvalidRlp(_) => Error::<T>::InvalidProofNode,
+			ProofError::KeyMismatch(_) => Error::<T>::ProofKeyMismatch,
+			ProofError::ValueMismatch(_) => Error::<T>::ProofValueMismatch,
+			ProofError::InvalidPrefix(_) => Error::<T>::InvalidPathPrefix,
+			ProofError::MissingNode(_) => Error::<T>::IncompleteProof,
+			ProofError::KeyPresent(_) => Error::<T>::ProofKeyPresent,
+			ProofError::UnusedNodes(_) => Error::<T>::UnusedProofNodes,
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// State root of the stored header of an Ethereum block.
+	pub fn state_root(eth_block_number: T::BlockNumber) -> Option<H256> {
+		<HeaderHashes<T>>::get(eth_block_number)
+			.and_then(Headers::get)
+			.map(|header| header.state_root)
+	}
+
+	/// Verify a storage proof of the token contract against the stored storage root of an Ethereum block.
+	pub fn verify_storage_proof(
+		eth_block_number: T::BlockNumber,
+		proof: &[Vec<u8>],
+		key: &[u8],
+		value: U256) -> DispatchResult
+	{
+		ensure!(key.len() <= T::MaxKeyLength::get() as usize, Error::<T>::KeyTooLong);
+		Self::ensure_proof_bounds(proof)?;
+		let storage_root = <StorageRoots<T>>::get(eth_block_number).ok_or(Error::<T>::UnknownStorageRoot)?;
+		verify_storage_value(storage_root.as_bytes(), proof, &keccak(key), value)
+			.map_err(|error| Error::<T>::from(error).into())
+	}
+
+	/// Verify a proof that a storage slot of the token contract is not set, against the stored storage root
+	/// of an Ethereum block.
+	pub fn verify_storage_absence(eth_block_number: T::BlockNumber, proof: &[Vec<u8>], key: &[u8]) -> DispatchResult {
+		ensure!(key.len() <= T::MaxKeyLength::get() as usize, Error::<T>::KeyTooLong);
+		Self::ensure_proof_bounds(proof)?;
+		let storage_root = <StorageRoots<T>>::get(eth_block_number).ok_or(Error::<T>::UnknownStorageRoot)?;
+		verify_absence(storage_root.as_bytes(), proof, &keccak(key)).map_err(|error| Error::<T>::from(error).into())
+	}
+
+	/// Read the value of a storage slot of the token contract from a storage proof against the stored
+	/// storage root of an Ethereum block. Unset slots read as zero.
+	pub fn read_storage_proof(
+		eth_block_number: T::BlockNumber,
+		proof: &[Vec<u8>],
+		key: &[u8]) -> Result<U256, Error<T>>
+	{
+		Self::ensure_proof_bounds(proof)?;
+		let storage_root = <StorageRoots<T>>::get(eth_block_number).ok_or(Error::<T>::UnknownStorageRoot)?;
+		read_storage_value(storage_root.as_bytes(), proof, &keccak(key)).map_err(Error::<T>::from)
+	}
+
+	/// Reject proofs with more than `T::MaxProofNodes` nodes or a node longer than `T::MaxNodeLength`
+	/// bytes, before spending any work on them. Without `BoundedVec`, the decoded proof is checked here.
+	pub fn ensure_proof_bounds(proof: &[Vec<u8>]) -> Result<(), Error<T>> {
+		Self::ensure_node_bounds(proof, T::MaxProofNodes::get())
+	}
+
+	fn ensure_node_bounds(nodes: &[Vec<u8>], max_nodes: u32) -> Result<(), Error<T>> {
+		ensure!(nodes.len() <= max_nodes as usize, Error::<T>::TooManyProofNodes);
+		let max_node_length = T::MaxNodeLength::get() as usize;
+		ensure!(nodes.iter().all(|node| node.len() <= max_node_length), Error::<T>::ProofNodeTooLong);
+		Ok(())
+	}
+
+	/// Verify the values of several storage slots of the token contract against the stored storage root of
+	/// an Ethereum block, with a single set of nodes shared by their storage proofs.
+	pub fn verify_storage_multi_proof(
+		eth_block_number: T::BlockNumber,
+		nodes: &[Vec<u8>],
+		items: &[(Vec<u8>, U256)]) -> DispatchResult
+	{
+		ensure!(items.len() <= T::MaxProofKeys::get() as usize, Error::<T>::TooManyProofKeys);
+		let max_key_length = T::MaxKeyLength::get() as usize;
+		ensure!(items.iter().all(|(key, _)| key.len() <= max_key_length), Error::<T>::KeyTooLong);
+		Self::ensure_node_bounds(nodes, T::MaxProofNodes::get().saturating_mul(T::MaxProofKeys::get()))?;
+		let storage_root = <StorageRoots<T>>::get(eth_block_number).ok_or(Error::<T>::UnknownStorageRoot)?;
+
+		// Storage trie values are big endian without leading zero bytes, and unset slots are not stored.
+		let items: Vec<(Vec<u8>, Vec<u8>)> = items.iter()
+			.map(|(key, value)| {
+				let mut bytes = [0u8; 32];
+				value.to_big_endian(&mut bytes);
+				(keccak(key).to_vec(), bytes[value.leading_zeros() as usize / 8..].to_vec())
+			})
+			.collect();
+		verify_multi_proof(storage_root.as_bytes(), nodes, &items).map_err(|error| Error::<T>::from(error).into())
+	}
+
+	/// Storage key of the token balance of `holder`, i.e. of `balances[holder]` in the token contract.
+	pub fn balance_key(holder: &H160) -> H256 {
+		address_mapping_slot(&slot(T::BalanceSlot::get()), holder)
+	}
+
+	/// The message that the Ethereum address claimed by `who` signs: a fixed tag, the genesis hash of the
+	/// chain and the SCALE encoded `who`.
+	pub fn claim_message(who: &T::AccountId) -> Vec<u8> {
+		let mut message = CLAIM_MESSAGE_TAG.to_vec();
+		message.extend_from_slice(<frame_system::Module<T>>::block_hash(T::BlockNumber::zero()).as_ref());
+		message.extend_from_slice(&who.encode());
+		message
+	}
+
+	/// Recover the Ethereum address that signed the claim message of `who` with `personal_sign` (EIP-191).
+	pub fn eth_recover(signature: &ecdsa::Signature, who: &T::AccountId) -> Option<H160> {
+		let message = Self::claim_message(who);
+		let mut prefixed = b"\x19Ethereum Signed Message:\n".to_vec();
+		// The message length is appended in decimal.
+		let mut length = message.len();
+		let mut digits = Vec::new();
+		loop {
+			digits.push(b'0' + (length % 10) as u8);
+			length /= 10;
+			if length == 0 {
+				break;
+			}
+		}
+		prefixed.extend(digits.iter().rev());
+		prefixed.extend_from_slice(&message);
+
+		let signature: &[u8; 65] = signature.as_ref();
+		let public = sp_io::crypto::secp256k1_ecdsa_recover(signature, &keccak(&prefixed)).ok()?;
+		Some(H160::from_slice(&keccak(&public)[12..]))
+	}
+
+	// Store a storage root and track its block number for pruning. Pending attestations for the block are
+	// removed later, in `prune`.
+	fn insert_storage_root(eth_block_number: T::BlockNumber, storage_root: StorageRoot) {
+		<StorageRoots<T>>::insert(eth_block_number, storage_root);
+		<StorageRootBlocks<T>>::mutate(|blocks| {
+			if let Err(index) = blocks.binary_search(&eth_block_number) {
+				blocks.insert(index, eth_block_number);
+			}
+		});
+		let mut attested = Self::attested_blocks();
+		if let Ok(index) = attested.binary_search(&eth_block_number) {
+			attested.remove(index);
+			<AttestedBlocks<T>>::put(attested);
+			<StaleAttestations<T>>::append((Self::relayer_set_index(), eth_block_number));
+		}
+	}
+
+	// Track the first pending attestation of the current relayer set for an Ethereum block. Beyond
+	// `T::MaxStoredRoots` tracked blocks, the attestations of the oldest one are queued for removal.
+	fn track_attested_block(eth_block_number: T::BlockNumber) {
+		let mut attested = Self::attested_blocks();
+		if let Err(index) = attested.binary_search(&eth_block_number) {
+			attested.insert(index, eth_block_number);
+		}
+		if attested.len() > T::MaxStoredRoots::get() as usize {
+			<StaleAttestations<T>>::append((Self::relayer_set_index(), attested.remove(0)));
+		}
+		<AttestedBlocks<T>>::put(attested);
+	}
+
+	// Remove the storage root of an Ethereum block.
+	fn remove_storage_root_of(eth_block_number: T::BlockNumber) {
+		<StorageRoots<T>>::remove(eth_block_number);
+		Self::deposit_event(RawEvent::StorageRootRemoved(eth_block_number));
+	}
+
+	// Remove the storage roots of the oldest Ethereum blocks beyond `T::MaxStoredRoots`, then the votes on
+	// closed proposals and the stale attestations, up to `T::MaxRemovals` items in total. Returns the number
+	// of removals made.
+	fn prune() -> u32 {
+		let mut blocks = Self::storage_root_blocks();
+		let excess = blocks.len()
+			.saturating_sub(T::MaxStoredRoots::get() as usize)
+			.min(T::MaxRemovals::get() as usize);
+		for eth_block_number in blocks.drain(..excess) {
+			// The roots of snapshots of open proposals are removed once the proposals close.
+			if !<SnapshotProposals<T>>::contains_key(eth_block_number) {
+				Self::remove_storage_root_of(eth_block_number);
+			}
+		}
+		if excess > 0 {
+			<StorageRootBlocks<T>>::put(blocks);
+		}
+
+		let mut removals = T::MaxRemovals::get() as usize - excess;
+		let mut closed = Self::closed_proposals();
+		let drain_votes = |index: &ProposalIndex, limit| <Votes<T>>::drain_prefix(index).take(limit).count();
+		if Self::drain_queue(&mut closed, &mut removals, drain_votes) > 0 {
+			ClosedProposals::put(closed);
+		}
+
+		let mut stale = Self::stale_attestations();
+		let drain_round = |round: &(u32, T::BlockNumber), limit| <Attestations<T>>::drain_prefix(round).take(limit).count();
+		if Self::drain_queue(&mut stale, &mut removals, drain_round) > 0 {
+			<StaleAttestations<T>>::put(stale);
+		}
+		T::MaxRemovals::get() - removals as u32
+	}
+
+	// Remove the items under the oldest prefixes in `queue` with `drain`, which removes at most the given
+	// number of items under a prefix and returns how many it removed. Popping a drained prefix counts as a
+	// removal too, so that at most `removals` are made in total. Returns the number of prefixes popped.
+	fn drain_queue<K>(
+		queue: &mut Vec<K>,
+		removals: &mut usize,
+		mut drain: impl FnMut(&K, usize) -> usize) -> usize
+	{
+		let mut popped = 0;
+		for prefix in queue.iter() {
+			if *removals == 0 {
+				break;
+			}
+			*removals -= drain(prefix, *removals);
+			if *removals == 0 {
+				break;
+			}
+			popped += 1;
+			*removals -= 1;
+		}
+		queue.drain(..popped);
+		popped
+	}
+
+	/// Whether the votes on a proposal reach `T::Quorum` and more than `T::Threshold` of them are ayes.
+	pub fn is_passing(info: &ProposalInfo<T::Proposal, T::AccountId, T::BlockNumber, BalanceOf<T>>) -> bool {
+		let turnout = info.ayes.saturating_add(info.nays);
+		turnout >= T::Quorum::get() &&
+			info.ayes.saturating_mul(Perbill::ACCURACY.into()) >
+			turnout.saturating_mul(T::Threshold::get().deconstruct().into())
+	}
+
+	// Tally a proposal whose voting period ended, and dispatch it if it passed. Its votes are removed later,
+	// in `prune`. Returns the weight used, including that of the dispatched proposal.
+	fn close_proposal(index: ProposalIndex) -> Weight {
+		let info = match <Proposals<T>>::take(index) {
+			Some(info) => info,
+			None => return T::DbWeight::get().reads(1),
+		};
+		ClosedProposals::append(index);
+		T::Currency::unreserve(&info.proposer, info.deposit);
+
+		// Remove the root of the snapshot if pruning kept it for the open proposals using it.
+		let open = Self::snapshot_proposals(info.snapshot).saturating_sub(1);
+		if open > 0 {
+			<SnapshotProposals<T>>::insert(info.snapshot, open);
+		} else {
+			<SnapshotProposals<T>>::remove(info.snapshot);
+			if <StorageRoots<T>>::contains_key(info.snapshot) &&
+				Self::storage_root_blocks().binary_search(&info.snapshot).is_err()
+			{
+				Self::remove_storage_root_of(info.snapshot);
+			}
+		}
+
+		let mut weight = T::WeightInfo::close_proposal();
+		if Self::is_passing(&info) {
+			Self::deposit_event(RawEvent::ProposalPassed(index));
+			weight = weight.saturating_add(info.proposal.get_dispatch_info().weight);
+			let result = info.proposal.dispatch(frame_system::RawOrigin::Root.into());
+			Self::deposit_event(RawEvent::ProposalExecuted(index, result.is_ok()));
+		} else {
+			Self::deposit_event(RawEvent::ProposalRejected(index));
+		}
+		weight
+	}
+
+	// Account proofs are verified against state roots rather than storage roots.
+	fn account_proof_error(error: ProofError) -> Error<T> {
+		match error {
+			ProofError::HashMismatch(0) => Error::<T>::StateRootMismatch,
+			error => error.into(),
+		}
 	}
 }
 
@@ -73,19 +631,29 @@ decl_module! {
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		// Prune old storage roots, votes and attestations, and tally the proposals whose voting period ends at
+		// this block.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let weight = T::WeightInfo::on_initialize(Self::prune());
+			<ProposalsEnding<T>>::take(n)
+				.into_iter()
+				.fold(weight, |weight, index| weight.saturating_add(Self::close_proposal(index)))
+		}
+
+		/// Store the storage root of the token contract at an Ethereum block. This function must be
+		/// dispatched by `T::SubmitOrigin`, and fails if a storage root is already stored for the block.
+		#[weight = T::WeightInfo::store_storage_root()]
 		pub fn store_storage_root(origin, eth_block_number: T::BlockNumber, storage_root: StorageRoot) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
+			// Check that the extrinsic comes from the configured origin.
 			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
-			let _ = ensure_signed(origin)?;
+			T::SubmitOrigin::ensure_origin(origin)?;
 
 			// let current_block_number = <frame_system::Pallet<T>>::block_number();
 
+			ensure!(!<StorageRoots<T>>::contains_key(eth_block_number), Error::<T>::StorageRootAlreadyStored);
+
 			// Update storage.
-			<StorageRoots<T>>::insert(eth_block_number, storage_root.clone());
+			Self::insert_storage_root(eth_block_number, storage_root);
 
 			// Emit an event.
 			Self::deposit_event(RawEvent::StorageRootStored(eth_block_number, storage_root));
@@ -93,33 +661,344 @@ decl_module! {
 			Ok(())
 		}
 
-		/// TODO: Think about how we can verify that a proof came from a specific Ethereum address.
-		/// TODO: Instead of simply verifying a proof, we should also pull out the `votes` value.
-		/// Verify proof. Inputs are byte arrays.
+		/// Replace the stored storage root of an Ethereum block, e.g. after a wrong root was stored.
+		/// This function must be dispatched by the root origin.
+		///
+		/// Vote weights registered against the old root can no longer be used to vote, and can be registered
+		/// again against the new one. Votes already cast with them are kept.
+		#[weight = T::WeightInfo::correct_storage_root()]
+		pub fn correct_storage_root(origin, eth_block_number: T::BlockNumber, storage_root: StorageRoot) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let old_storage_root = <StorageRoots<T>>::get(eth_block_number).ok_or(Error::<T>::UnknownStorageRoot)?;
+			<StorageRoots<T>>::insert(eth_block_number, storage_root);
+
+			Self::deposit_event(RawEvent::StorageRootCorrected(eth_block_number, old_storage_root, storage_root));
+			Ok(())
+		}
+
+		/// Remove the stored storage root of an Ethereum block. This function must be dispatched by the
+		/// root origin.
+		#[weight = T::WeightInfo::remove_storage_root()]
+		pub fn remove_storage_root(origin, eth_block_number: T::BlockNumber) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(<StorageRoots<T>>::contains_key(eth_block_number), Error::<T>::UnknownStorageRoot);
+			<StorageRootBlocks<T>>::mutate(|blocks| blocks.retain(|block| *block != eth_block_number));
+			Self::remove_storage_root_of(eth_block_number);
+			Ok(())
+		}
+
+		/// Replace the relayer set and threshold. Pending attestations are ignored from then on, so that
+		/// roots are only finalized by attestations of the new set, and removed over the next blocks. This
+		/// function must be dispatched by `T::SubmitOrigin`.
+		///
+		/// * `relayers` - Accounts allowed to attest storage roots, at most `T::MaxRelayers`.
+		/// * `threshold` - Number of relayers that must attest the same storage root before it is stored.
+		#[weight = T::WeightInfo::set_relayers(relayers.len() as u32)]
+		pub fn set_relayers(origin, relayers: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+			T::SubmitOrigin::ensure_origin(origin)?;
+
+			ensure!(relayers.len() <= T::MaxRelayers::get() as usize, Error::<T>::TooManyRelayers);
+			let mut relayers = relayers;
+			relayers.sort();
+			relayers.dedup();
+			ensure!(threshold > 0 && threshold as usize <= relayers.len(), Error::<T>::InvalidThreshold);
+
+			let index = Self::relayer_set_index();
+			let attested = <AttestedBlocks<T>>::take();
+			if !attested.is_empty() {
+				<StaleAttestations<T>>::mutate(|stale| stale.extend(attested.into_iter().map(|block| (index, block))));
+			}
+			<Relayers<T>>::put(&relayers);
+			RelayerThreshold::put(threshold);
+			RelayerSetIndex::put(index.wrapping_add(1));
+
+			Self::deposit_event(RawEvent::RelayersSet(relayers, threshold));
+			Ok(())
+		}
+
+		/// Attest the storage root of the token contract at an Ethereum block. The root is stored once
+		/// `RelayerThreshold` relayers attested it. This function must be dispatched by a relayer.
+		///
+		/// Each relayer attests one root per block, so at most `T::MaxRelayers` attested roots are read.
+		/// They are removed over the next blocks once a root is stored for the block.
+		#[weight = T::WeightInfo::attest_storage_root(T::MaxRelayers::get())]
+		pub fn attest_storage_root(origin, eth_block_number: T::BlockNumber, storage_root: StorageRoot) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+
+			ensure!(Self::relayers().binary_search(&relayer).is_ok(), Error::<T>::NotRelayer);
+			ensure!(!<StorageRoots<T>>::contains_key(eth_block_number), Error::<T>::StorageRootAlreadyStored);
+			let round = (Self::relayer_set_index(), eth_block_number);
+			let mut conflicting_root = None;
+			let mut first = true;
+			for (attested_root, attesters) in <Attestations<T>>::iter_prefix(round) {
+				first = false;
+				ensure!(!attesters.contains(&relayer), Error::<T>::AlreadyAttested);
+				if attested_root != storage_root {
+					conflicting_root = Some(attested_root);
+				}
+			}
+
+			let mut attesters = <Attestations<T>>::get(round, storage_root);
+			attesters.push(relayer.clone());
+			Self::deposit_event(RawEvent::StorageRootAttested(relayer.clone(), eth_block_number, storage_root));
+			if let Some(conflicting_root) = conflicting_root {
+				Self::deposit_event(RawEvent::AttestationConflict(relayer, eth_block_number, storage_root, conflicting_root));
+			}
+
+			if attesters.len() >= Self::relayer_threshold() as usize {
+				Self::insert_storage_root(eth_block_number, storage_root);
+				Self::deposit_event(RawEvent::StorageRootFinalized(eth_block_number, storage_root));
+			} else {
+				<Attestations<T>>::insert(round, storage_root, attesters);
+				if first {
+					Self::track_attested_block(eth_block_number);
+				}
+			}
+			Ok(())
+		}
+
+		/// Store an Ethereum block header, indexed by both its hash and its number. Account proofs are
+		/// verified against the state roots of stored headers. This function must be dispatched by
+		/// `T::SubmitOrigin`.
+		///
+		/// * `block_hash` - Hash of the block, which must be the keccak hash of `header`.
+		/// * `header` - The RLP encoded block header.
+		#[weight = T::WeightInfo::submit_header()]
+		pub fn submit_header(origin, block_hash: H256, header: Vec<u8>) -> DispatchResult {
+			T::SubmitOrigin::ensure_origin(origin)?;
+
+			let header = Header::decode(&header).map_err(|_| Error::<T>::InvalidHeader)?;
+			ensure!(header.hash == block_hash, Error::<T>::HeaderHashMismatch);
+			let eth_block_number = T::BlockNumber::try_from(header.number).map_err(|_| Error::<T>::InvalidHeader)?;
+			ensure!(!<HeaderHashes<T>>::contains_key(eth_block_number), Error::<T>::HeaderAlreadyStored);
+
+			Headers::insert(block_hash, EthHeader::from(header));
+			<HeaderHashes<T>>::insert(eth_block_number, block_hash);
+
+			Self::deposit_event(RawEvent::HeaderStored(eth_block_number, block_hash));
+			Ok(())
+		}
+
+		/// Prove the storage root of the token contract with an account proof against the state root
+		/// of a stored Ethereum block header, and store it. Unlike `store_storage_root`, the storage root is derived from
+		/// the proven account instead of being trusted from the signer.
+		///
+		/// * `eth_block_number` - Ethereum block number that the proof comes from.
+		/// * `account_proof` - The `accountProof` returned by `eth_getProof` for the token contract, starting with the state root node.
+		#[weight = T::WeightInfo::prove_storage_root(account_proof.len() as u32, proof_size(account_proof))]
+		pub fn prove_storage_root(origin, eth_block_number: T::BlockNumber, account_proof: Vec<Vec<u8>>) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			Self::ensure_proof_bounds(&account_proof)?;
+			ensure!(!<StorageRoots<T>>::contains_key(eth_block_number), Error::<T>::StorageRootAlreadyStored);
+			let state_root = Self::state_root(eth_block_number).ok_or(Error::<T>::UnknownHeader)?;
+			let account = verify_account_proof(&state_root, &account_proof, &T::TokenContract::get())
+				.map_err(Self::account_proof_error)?
+				.ok_or(Error::<T>::AccountNotFound)?;
+
+			Self::insert_storage_root(eth_block_number, account.storage_root);
+
+			Self::deposit_event(RawEvent::StorageRootStored(eth_block_number, account.storage_root));
+			Ok(())
+		}
+
+		/// Verify proof. Inputs are byte arrays. Fails with `UnknownStorageRoot` if no storage root is stored
+		/// for the block, and with the reason the proof is invalid otherwise.
 		///
 		/// * `block_number` - Ethereum block number that the proof comes from.
 		/// * `proof` - Vector of proofs, where each proof is a RLP-serialized MerkleTree-Node, starting with the storage hash node.
 		/// * `key` - The storage key.
-		/// * `val` - The value stored at the storage key.
-		#[weight = 10_000]
-		pub fn verify_proof(origin, block_number: T::BlockNumber, proof: Vec<Vec<u8>>, key: Vec<u8>, value: Vec<u8>) -> DispatchResult {
+		/// * `value` - The value stored at the storage key. Zero proves that the storage key is unset.
+		#[weight = T::WeightInfo::verify_proof(proof.len() as u32, proof_size(proof))]
+		pub fn verify_proof(origin, block_number: T::BlockNumber, proof: Vec<Vec<u8>>, key: Vec<u8>, value: U256) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
 			let _ = ensure_signed(origin)?;
 
-			// Get storage root at block number storage.
-			let storage_root = <StorageRoots<T>>::get(block_number);
-
-			let hashed_key = verifier::keccak(&key);
-			let hex_string_key = hex::encode(hashed_key);
-			let _is_verified = match verifier::verify_merkle_proof(&storage_root.as_bytes().to_vec(), proof, hex_string_key, value, 0, 0) {
-				true => Self::deposit_event(RawEvent::VerifyProof(true)),
-				false => Self::deposit_event(RawEvent::VerifyProof(false)), // RETURN DISPATCHRESULT WITH ERROR HERE
-			};
+			Self::verify_storage_proof(block_number, &proof, &key, value)?;
+			Self::deposit_event(RawEvent::VerifyProof(true));
 
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
+
+		/// Verify a proof of `mapping[holder]` for a `mapping(address => uint)` of the token contract.
+		/// Unlike `verify_proof`, the storage key is derived from the holder, so the proof is tied to them.
+		///
+		/// * `block_number` - Ethereum block number that the proof comes from.
+		/// * `holder` - The Ethereum address used as mapping key.
+		/// * `mapping_slot` - Position of the mapping in the storage layout of the token contract.
+		/// * `proof` - The storage proof, starting with the storage root node.
+		/// * `value` - The value stored for the holder.
+		#[weight = T::WeightInfo::verify_proof(proof.len() as u32, proof_size(proof))]
+		pub fn verify_mapping_proof(
+			origin,
+			block_number: T::BlockNumber,
+			holder: H160,
+			mapping_slot: u64,
+			proof: Vec<Vec<u8>>,
+			value: U256,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			let key = address_mapping_slot(&slot(mapping_slot), &holder);
+			Self::verify_storage_proof(block_number, &proof, key.as_bytes(), value)?;
+			Self::deposit_event(RawEvent::VerifyProof(true));
+			Ok(())
+		}
+
+		/// Verify that a storage slot of the token contract is not set at an Ethereum block, failing with
+		/// `ProofKeyPresent` if it is set. As for `verify_proof`, the proof must end at the node showing that
+		/// the slot is absent.
+		///
+		/// * `block_number` - Ethereum block number that the proof comes from.
+		/// * `proof` - The storage proof returned by `eth_getProof` for the unset slot, starting with the storage root node.
+		/// * `key` - The storage key.
+		#[weight = T::WeightInfo::verify_proof(proof.len() as u32, proof_size(proof))]
+		pub fn verify_non_inclusion(origin, block_number: T::BlockNumber, proof: Vec<Vec<u8>>, key: Vec<u8>) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			Self::verify_storage_absence(block_number, &proof, &key)?;
+			Self::deposit_event(RawEvent::VerifyProof(true));
+			Ok(())
+		}
+
+		/// Verify the values of several storage slots of the token contract at once. Their storage proofs
+		/// share the upper trie nodes, which are submitted and hashed only once.
+		///
+		/// * `block_number` - Ethereum block number that the proofs come from.
+		/// * `nodes` - The nodes of the storage proofs of all slots, e.g. of an `eth_getProof` call with several
+		///   storage keys, deduplicated and in any order.
+		/// * `items` - The storage keys and their values. Zero proves that a storage key is unset.
+		#[weight = T::WeightInfo::verify_multi_proof(items.len() as u32, nodes.len() as u32, proof_size(nodes))]
+		pub fn verify_multi_proof(
+			origin,
+			block_number: T::BlockNumber,
+			nodes: Vec<Vec<u8>>,
+			items: Vec<(Vec<u8>, U256)>,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			Self::verify_storage_multi_proof(block_number, &nodes, &items)?;
+			Self::deposit_event(RawEvent::VerifyProof(true));
+			Ok(())
+		}
+
+		/// Bind an Ethereum address to the signer, so that token balances of the address can be
+		/// registered as vote weight of the signer.
+		///
+		/// * `signature` - `personal_sign` (EIP-191) signature of the claim message of the signer, see
+		///   `claim_message`, by the Ethereum address.
+		#[weight = T::WeightInfo::claim_eth_address()]
+		pub fn claim_eth_address(origin, signature: ecdsa::Signature) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let address = Self::eth_recover(&signature, &who).ok_or(Error::<T>::InvalidSignature)?;
+			ensure!(!<EthAddresses<T>>::contains_key(&who), Error::<T>::AccountAlreadyClaimed);
+			ensure!(!<EthAccounts<T>>::contains_key(address), Error::<T>::EthAddressAlreadyClaimed);
+
+			<EthAddresses<T>>::insert(&who, address);
+			<EthAccounts<T>>::insert(address, &who);
+
+			Self::deposit_event(RawEvent::EthAddressClaimed(who, address));
+			Ok(())
+		}
+
+		/// Register the vote weight of the signer at an Ethereum snapshot block, by proving the token
+		/// balance of the Ethereum address claimed by the signer.
+		///
+		/// * `snapshot` - Ethereum block number that the proof comes from.
+		/// * `proof` - The storage proof of the balance, starting with the storage root node. The proven
+		///   balance becomes the vote weight.
+		#[weight = T::WeightInfo::register_votes(proof.len() as u32, proof_size(proof))]
+		pub fn register_votes(origin, snapshot: T::BlockNumber, proof: Vec<Vec<u8>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let storage_root = <StorageRoots<T>>::get(snapshot).ok_or(Error::<T>::UnknownStorageRoot)?;
+			ensure!(
+				Self::vote_weight(&who, snapshot).map_or(true, |(_, proven_root)| proven_root != storage_root),
+				Error::<T>::VotesAlreadyRegistered
+			);
+			// Claims are permanent and one-to-one, so the balance slot of the address is only credited to the
+			// signer, and at most once per root by the check above.
+			let address = <EthAddresses<T>>::get(&who).ok_or(Error::<T>::NoEthAddress)?;
+			let weight = Self::read_storage_proof(snapshot, &proof, Self::balance_key(&address).as_bytes())?;
+			ensure!(!weight.is_zero(), Error::<T>::NoVoteWeight);
+
+			<VoteWeights<T>>::insert(&who, snapshot, (weight, storage_root));
+
+			Self::deposit_event(RawEvent::VotesRegistered(who, snapshot, weight));
+			Ok(())
+		}
+
+		/// Propose a call to be dispatched with the root origin, voted on with the vote weights registered
+		/// at an Ethereum snapshot block. Voting ends after `T::VotingPeriod` blocks, when the
+		/// `T::ProposalDeposit` reserved from the proposer is returned.
+		///
+		/// * `proposal` - The call to dispatch if the proposal passes.
+		/// * `snapshot` - Ethereum block number with a stored storage root, whose token balances are the vote weights.
+		#[weight = T::WeightInfo::propose(proposal.encoded_size() as u32)]
+		pub fn propose(origin, proposal: Box<T::Proposal>, snapshot: T::BlockNumber) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+
+			ensure!(proposal.encoded_size() <= T::MaxProposalLength::get() as usize, Error::<T>::ProposalTooLong);
+			ensure!(
+				proposal.get_dispatch_info().weight <= T::MaxProposalWeight::get(),
+				Error::<T>::ProposalTooHeavy
+			);
+			ensure!(<StorageRoots<T>>::contains_key(snapshot), Error::<T>::UnknownStorageRoot);
+			let index = Self::proposal_count();
+			let end = <frame_system::Module<T>>::block_number() + T::VotingPeriod::get();
+			let mut ending = Self::proposals_ending(end);
+			ensure!(ending.len() < T::MaxProposals::get() as usize, Error::<T>::TooManyProposals);
+
+			let deposit = T::ProposalDeposit::get();
+			T::Currency::reserve(&proposer, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			ending.push(index);
+			<ProposalsEnding<T>>::insert(end, ending);
+			<SnapshotProposals<T>>::mutate(snapshot, |open| *open += 1);
+			ProposalCount::put(index + 1);
+			<Proposals<T>>::insert(index, ProposalInfo {
+				proposer: proposer.clone(),
+				deposit,
+				proposal: *proposal,
+				snapshot,
+				end,
+				ayes: U256::zero(),
+				nays: U256::zero(),
+			});
+
+			Self::deposit_event(RawEvent::Proposed(index, proposer, snapshot));
+			Ok(())
+		}
+
+		/// Vote on a proposal with the vote weight that the signer registered at its snapshot block.
+		#[weight = T::WeightInfo::vote()]
+		pub fn vote(origin, proposal_index: ProposalIndex, aye: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut info = Self::proposal(proposal_index).ok_or(Error::<T>::UnknownProposal)?;
+			ensure!(!<Votes<T>>::contains_key(proposal_index, &who), Error::<T>::AlreadyVoted);
+			let (weight, proven_root) = Self::vote_weight(&who, info.snapshot).ok_or(Error::<T>::VotesNotRegistered)?;
+			// Vote weights of pruned roots stay valid, those of corrected roots do not.
+			ensure!(
+				Self::storage_root(info.snapshot).map_or(true, |storage_root| storage_root == proven_root),
+				Error::<T>::VotesNotRegistered
+			);
+
+			if aye {
+				info.ayes = info.ayes.saturating_add(weight);
+			} else {
+				info.nays = info.nays.saturating_add(weight);
+			}
+			<Proposals<T>>::insert(proposal_index, info);
+			<Votes<T>>::insert(proposal_index, &who, aye);
+
+			Self::deposit_event(RawEvent::Voted(who, proposal_index, aye, weight));
+			Ok(())
+		}
 	}
 }
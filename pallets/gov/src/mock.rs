@@ -1,8 +1,8 @@
 use crate as pallet_gov;
-use sp_core::H256;
-use frame_support::parameter_types;
+use sp_core::{H160, H256, U256};
+use frame_support::{parameter_types, weights::Weight};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
 use frame_system as system;
 
@@ -17,6 +17,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		GovModule: pallet_gov::{Module, Call, Storage, Event<T>},
 	}
 );
@@ -44,20 +45,80 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = SS58Prefix;
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	// COMP token contract.
+	pub const TokenContract: H160 = H160([
+		0xc0, 0x0e, 0x94, 0xcb, 0x66, 0x2c, 0x35, 0x20, 0x28, 0x2e,
+		0x6f, 0x57, 0x17, 0x21, 0x40, 0x04, 0xa7, 0xf2, 0x68, 0x88,
+	]);
+	pub const BalanceSlot: u64 = 1;
+	pub const MaxStoredRoots: u32 = 3;
+	pub const MaxRemovals: u32 = 2;
+	pub const MaxProofNodes: u32 = 8;
+	pub const MaxNodeLength: u32 = 600;
+	pub const MaxKeyLength: u32 = 32;
+	pub const MaxProofKeys: u32 = 4;
+	pub const MaxRelayers: u32 = 4;
+	pub const ProposalDeposit: u64 = 100;
+	pub const MaxProposals: u32 = 2;
+	pub const MaxProposalWeight: Weight = 1_000_000_000;
+	pub const MaxProposalLength: u32 = 256;
+	pub const VotingPeriod: u64 = 5;
+	pub Quorum: U256 = U256::exp10(18) * 500u64;
+	pub const Threshold: Perbill = Perbill::from_percent(50);
+}
+
 impl pallet_gov::Config for Test {
 	type Event = Event;
+	type TokenContract = TokenContract;
+	type BalanceSlot = BalanceSlot;
+	type MaxStoredRoots = MaxStoredRoots;
+	type MaxRemovals = MaxRemovals;
+	type MaxProofNodes = MaxProofNodes;
+	type MaxNodeLength = MaxNodeLength;
+	type MaxKeyLength = MaxKeyLength;
+	type MaxProofKeys = MaxProofKeys;
+	type MaxRelayers = MaxRelayers;
+	type Proposal = Call;
+	type Currency = Balances;
+	type ProposalDeposit = ProposalDeposit;
+	type MaxProposals = MaxProposals;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxProposalLength = MaxProposalLength;
+	type VotingPeriod = VotingPeriod;
+	type Quorum = Quorum;
+	type Threshold = Threshold;
+	type SubmitOrigin = system::EnsureRoot<u64>;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1_000), (2, 1_000), (3, 50)],
+	}.assimilate_storage(&mut t).unwrap();
     let mut ext: sp_io::TestExternalities = t.into();
 	ext.execute_with(|| System::set_block_number(1));
 	ext
@@ -0,0 +1,220 @@
+//! Weights of the pallet-gov extrinsics that are benchmarked in `benchmarking.rs`.
+//!
+//! These are provisional estimates, not the output of a benchmark run. Replace them with the output of
+//! `node-template benchmark --chain dev --execution wasm --wasm-execution compiled --pallet pallet_gov
+//! --extrinsic '*' --steps 50 --repeat 20 --output <file>`, run on reference hardware with a node built
+//! with the `runtime-benchmarks` feature.
+
+#![allow(unused_parens)]
+
+use core::marker::PhantomData;
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+
+/// Weight functions needed for pallet-gov. `n` is the number of proof nodes, `b` the total
+/// length of the proof nodes in bytes, `k` the number of proven storage keys, `r` the number of
+/// relayers, `l` the length of the encoded proposal in bytes and `d` the number of removals made by
+/// `on_initialize`. `close_proposal` excludes the dispatch of the proposal.
+pub trait WeightInfo {
+	fn store_storage_root() -> Weight;
+	fn correct_storage_root() -> Weight;
+	fn remove_storage_root() -> Weight;
+	fn set_relayers(r: u32) -> Weight;
+	fn attest_storage_root(r: u32) -> Weight;
+	fn submit_header() -> Weight;
+	fn prove_storage_root(n: u32, b: u32) -> Weight;
+	fn verify_proof(n: u32, b: u32) -> Weight;
+	fn verify_multi_proof(k: u32, n: u32, b: u32) -> Weight;
+	fn register_votes(n: u32, b: u32) -> Weight;
+	fn claim_eth_address() -> Weight;
+	fn propose(l: u32) -> Weight;
+	fn vote() -> Weight;
+	fn on_initialize(d: u32) -> Weight;
+	fn close_proposal() -> Weight;
+}
+
+/// Weights for pallet-gov using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn store_storage_root() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn correct_storage_root() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_storage_root() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_relayers(r: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn attest_storage_root(r: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn submit_header() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn prove_storage_root(n: u32, b: u32) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn verify_proof(n: u32, b: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	fn verify_multi_proof(k: u32, n: u32, b: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((1_900_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	fn register_votes(n: u32, b: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_eth_address() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn propose(l: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn vote() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize(d: u32) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	fn close_proposal() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
+
+// For tests.
+impl WeightInfo for () {
+	fn store_storage_root() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn correct_storage_root() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_storage_root() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_relayers(r: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn attest_storage_root(r: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn submit_header() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn prove_storage_root(n: u32, b: u32) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn verify_proof(n: u32, b: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn verify_multi_proof(k: u32, n: u32, b: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((1_900_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn register_votes(n: u32, b: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((6_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn claim_eth_address() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn propose(l: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn vote() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize(d: u32) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	fn close_proposal() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+}
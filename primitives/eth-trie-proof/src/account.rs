@@ -0,0 +1,58 @@
+use primitive_types::{H160, H256, U256};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+use crate::{keccak, verifier::lookup, ProofError, ProofMode};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An Ethereum account as stored in the state trie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+	pub nonce: U256,
+	pub balance: U256,
+	/// Root of the account's storage trie.
+	pub storage_root: H256,
+	/// Hash of the account's EVM code.
+	pub code_hash: H256,
+}
+
+impl Decodable for Account {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 4 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		Ok(Account {
+			nonce: rlp.val_at(0)?,
+			balance: rlp.val_at(1)?,
+			storage_root: rlp.val_at(2)?,
+			code_hash: rlp.val_at(3)?,
+		})
+	}
+}
+
+impl Encodable for Account {
+	fn rlp_append(&self, stream: &mut RlpStream) {
+		stream.begin_list(4)
+			.append(&self.nonce)
+			.append(&self.balance)
+			.append(&self.storage_root)
+			.append(&self.code_hash);
+	}
+}
+
+/// Verify the account proof (`accountProof` of `eth_getProof`) of `address` against `state_root`.
+/// Returns the proven account, or `None` if the proof shows that the account does not exist.
+pub fn verify_account_proof(
+	state_root: &H256,
+	proof: &[Vec<u8>],
+	address: &H160) -> Result<Option<Account>, ProofError>
+{
+	let key = keccak(address.as_bytes());
+	match lookup(state_root.as_bytes(), proof, &key, ProofMode::Strict)? {
+		(proof_index, Some(value)) => rlp::decode(value)
+			.map_err(|_| ProofError::InvalidRlp(proof_index))
+			.map(Some),
+		(_, None) => Ok(None),
+	}
+}
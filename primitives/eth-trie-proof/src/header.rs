@@ -0,0 +1,50 @@
+use primitive_types::H256;
+use rlp::{DecoderError, Rlp};
+
+use crate::keccak;
+
+// Positions of the fields in the RLP list of a block header.
+const PARENT_HASH: usize = 0;
+const STATE_ROOT: usize = 3;
+const TRANSACTIONS_ROOT: usize = 4;
+const RECEIPTS_ROOT: usize = 5;
+const NUMBER: usize = 8;
+const TIMESTAMP: usize = 11;
+// Headers before the London fork have 15 fields, later forks append more.
+const MIN_HEADER_FIELDS: usize = 15;
+
+/// The fields of an Ethereum block header that proofs are verified against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+	/// Keccak hash of the RLP encoded header.
+	pub hash: H256,
+	pub parent_hash: H256,
+	/// Root of the state trie, which account proofs are verified against.
+	pub state_root: H256,
+	pub transactions_root: H256,
+	pub receipts_root: H256,
+	pub number: u64,
+	pub timestamp: u64,
+}
+
+impl Header {
+	/// Decode a RLP encoded block header. Never panics.
+	pub fn decode(rlp_header: &[u8]) -> Result<Self, DecoderError> {
+		let header = Rlp::new(rlp_header);
+		if header.payload_info()?.total() != rlp_header.len() {
+			return Err(DecoderError::RlpInconsistentLengthAndData);
+		}
+		if header.item_count()? < MIN_HEADER_FIELDS {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		Ok(Header {
+			hash: H256(keccak(rlp_header)),
+			parent_hash: header.val_at(PARENT_HASH)?,
+			state_root: header.val_at(STATE_ROOT)?,
+			transactions_root: header.val_at(TRANSACTIONS_ROOT)?,
+			receipts_root: header.val_at(RECEIPTS_ROOT)?,
+			number: header.val_at(NUMBER)?,
+			timestamp: header.val_at(TIMESTAMP)?,
+		})
+	}
+}
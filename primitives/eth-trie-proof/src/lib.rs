@@ -0,0 +1,42 @@
+//! Verification of Ethereum Merkle-Patricia trie proofs, as returned by `eth_getProof`.
+//!
+//! The crate is `no_std` so that the same code checks proofs in the runtime and off-chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+mod account;
+mod header;
+mod nibble;
+mod node;
+mod slot;
+mod verifier;
+#[cfg(feature = "std")]
+pub mod rpc;
+
+#[cfg(test)]
+mod tests;
+
+pub use account::{verify_account_proof, Account};
+pub use header::Header;
+pub use nibble::NibbleSlice;
+pub use node::{decode_compact_path, Node, NodeError};
+pub use slot::{
+	address_key, address_mapping_slot, array_element_slot, mapping_slot, slot, struct_member_slot, uint_key,
+};
+pub use verifier::{
+	read_proof, read_storage_value, verify_absence, verify_merkle_proof, verify_multi_proof, verify_raw_value,
+	verify_storage_value, ProofError, ProofMode,
+};
+
+/// Keccak-256 hash of `bytes`.
+pub fn keccak(bytes: &[u8]) -> [u8; 32] {
+	use tiny_keccak::Hasher;
+	let mut hasher = tiny_keccak::Keccak::v256();
+	let mut hash = [0u8; 32];
+	hasher.update(bytes);
+	hasher.finalize(&mut hash);
+	hash
+}
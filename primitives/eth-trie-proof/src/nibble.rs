@@ -0,0 +1,53 @@
+/// A view onto the nibbles (half bytes) of a byte slice, starting at nibble `offset`.
+#[derive(Clone, Copy, Debug)]
+pub struct NibbleSlice<'a> {
+	data: &'a [u8],
+	offset: usize,
+}
+
+impl<'a> NibbleSlice<'a> {
+	/// All nibbles of `data`, high nibble first.
+	pub fn new(data: &'a [u8]) -> Self {
+		NibbleSlice { data, offset: 0 }
+	}
+
+	/// The nibbles of `data` after the first `offset` nibbles. Panics if `offset > 2 * data.len()`.
+	pub fn new_offset(data: &'a [u8], offset: usize) -> Self {
+		NibbleSlice::new(data).mid(offset)
+	}
+
+	/// Number of nibbles in the slice.
+	pub fn len(&self) -> usize {
+		self.data.len() * 2 - self.offset
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Nibble at index `i`. Panics if `i >= self.len()`.
+	pub fn at(&self, i: usize) -> u8 {
+		let nibble_index = self.offset + i;
+		let byte = self.data[nibble_index / 2];
+		if nibble_index & 1 == 0 { byte >> 4 } else { byte & 0x0f }
+	}
+
+	/// The nibbles after the first `i` nibbles. Panics if `i > self.len()`.
+	pub fn mid(&self, i: usize) -> Self {
+		assert!(i <= self.len());
+		NibbleSlice { data: self.data, offset: self.offset + i }
+	}
+
+	/// Whether the slice starts with all nibbles of `prefix`.
+	pub fn starts_with(&self, prefix: &NibbleSlice) -> bool {
+		self.len() >= prefix.len() && (0..prefix.len()).all(|i| self.at(i) == prefix.at(i))
+	}
+}
+
+impl<'a> PartialEq for NibbleSlice<'a> {
+	fn eq(&self, other: &Self) -> bool {
+		self.len() == other.len() && self.starts_with(other)
+	}
+}
+
+impl<'a> Eq for NibbleSlice<'a> {}
@@ -0,0 +1,90 @@
+use rlp::Rlp;
+
+use crate::NibbleSlice;
+
+const EVEN_EXTENSION_PREFIX: u8 = 0;
+const ODD_EXTENSION_PREFIX: u8 = 1;
+const EVEN_LEAF_PREFIX: u8 = 2;
+const ODD_LEAF_PREFIX: u8 = 3;
+
+/// Reason an RLP encoded trie node could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeError {
+	/// The node is not an RLP list of 2 or 17 items, or a child reference is neither a 32 byte hash nor an
+	/// embedded node shorter than 32 bytes.
+	InvalidRlp,
+	/// The compact encoded path of a leaf or extension node has an unknown prefix.
+	InvalidPrefix,
+}
+
+/// A trie node decoded from its RLP encoding, which it borrows from.
+/// See appendix D of the Ethereum yellow paper for the node composition.
+// Nodes only live on the stack while walking a proof, so the large branch variant is not boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Node<'a> {
+	/// References to the 16 children indexed by the next key nibble (empty if there is no child),
+	/// and the value of the key ending at this node (empty if there is none).
+	/// A reference is the 32 byte hash of the child node, or the RLP encoding of a child node shorter
+	/// than 32 bytes, which is embedded in its parent.
+	Branch { children: [&'a [u8]; 16], value: &'a [u8] },
+	/// Key nibbles shared by all keys below this node, and the reference to the child node.
+	Extension { path: NibbleSlice<'a>, child: &'a [u8] },
+	/// The remaining key nibbles and the RLP encoded value.
+	Leaf { path: NibbleSlice<'a>, value: &'a [u8] },
+}
+
+impl<'a> Node<'a> {
+	/// Decode a RLP encoded node. Never panics.
+	pub fn decode(rlp_node: &'a [u8]) -> Result<Self, NodeError> {
+		let node = Rlp::new(rlp_node);
+		let item = |index| node.at(index).and_then(|item| item.data()).map_err(|_| NodeError::InvalidRlp);
+		let reference = |index| {
+			let item = node.at(index).map_err(|_| NodeError::InvalidRlp)?;
+			if item.is_list() {
+				// Child nodes shorter than 32 bytes are embedded in their parent instead of being hashed.
+				Some(item.as_raw()).filter(|child| child.len() < 32).ok_or(NodeError::InvalidRlp)
+			} else {
+				item.data().ok()
+					.filter(|child| child.is_empty() || child.len() == 32)
+					.ok_or(NodeError::InvalidRlp)
+			}
+		};
+		match node.item_count().map_err(|_| NodeError::InvalidRlp)? {
+			17 => {
+				let mut children: [&[u8]; 16] = [&[]; 16];
+				for (index, child) in children.iter_mut().enumerate() {
+					*child = reference(index)?;
+				}
+				Ok(Node::Branch { children, value: item(16)? })
+			},
+			2 => {
+				let (path, is_leaf) = decode_compact_path(item(0)?).ok_or(NodeError::InvalidPrefix)?;
+				if is_leaf {
+					Ok(Node::Leaf { path, value: item(1)? })
+				} else if path.is_empty() {
+					// An extension node always shares at least one nibble with its child.
+					Err(NodeError::InvalidPrefix)
+				} else {
+					Ok(Node::Extension { path, child: reference(1)? })
+				}
+			},
+			_ => Err(NodeError::InvalidRlp),
+		}
+	}
+}
+
+/// Decode the compact (hex-prefix) encoded path of a leaf or extension node.
+/// Returns the path nibbles and whether the node is a leaf.
+pub fn decode_compact_path(encoded: &[u8]) -> Option<(NibbleSlice<'_>, bool)> {
+	let first_byte = encoded.first()?;
+	match first_byte >> 4 {
+		// Even paths pad the prefix nibble with a zero nibble.
+		EVEN_EXTENSION_PREFIX | EVEN_LEAF_PREFIX if first_byte & 0x0f == 0 =>
+			Some((NibbleSlice::new_offset(encoded, 2), first_byte >> 4 == EVEN_LEAF_PREFIX)),
+		// Odd paths store their first nibble right after the prefix nibble.
+		ODD_EXTENSION_PREFIX | ODD_LEAF_PREFIX =>
+			Some((NibbleSlice::new_offset(encoded, 1), first_byte >> 4 == ODD_LEAF_PREFIX)),
+		_ => None,
+	}
+}
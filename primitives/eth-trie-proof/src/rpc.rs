@@ -0,0 +1,68 @@
+//! Typed `eth_getProof` JSON-RPC responses, for tooling that submits proofs to the chain.
+
+use primitive_types::{H160, H256, U256};
+use serde::{de::Error, Deserialize, Deserializer};
+
+/// The result of an `eth_getProof` call.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofResponse {
+	pub address: H160,
+	/// Account proof of `address`, starting with the state root node.
+	#[serde(deserialize_with = "deserialize_nodes")]
+	pub account_proof: Vec<Vec<u8>>,
+	pub balance: U256,
+	pub code_hash: H256,
+	pub nonce: U256,
+	/// Storage root of the account.
+	pub storage_hash: H256,
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// The proof of a single storage slot in an `eth_getProof` response.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct StorageProof {
+	/// The storage slot, as requested.
+	pub key: U256,
+	pub value: U256,
+	/// Storage proof of `key`, starting with the storage root node.
+	#[serde(deserialize_with = "deserialize_nodes")]
+	pub proof: Vec<Vec<u8>>,
+}
+
+impl ProofResponse {
+	/// Parse the `result` of an `eth_getProof` JSON-RPC response.
+	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(json)
+	}
+}
+
+impl StorageProof {
+	/// The 32 byte storage slot, as passed to the `key` argument of `GovModule::verify_proof`.
+	pub fn key_bytes(&self) -> Vec<u8> {
+		let mut key = [0u8; 32];
+		self.key.to_big_endian(&mut key);
+		key.to_vec()
+	}
+
+	/// The value without leading zero bytes, as stored in the storage trie and passed to the
+	/// `expected_value` argument of `verify_merkle_proof`. Empty for a zero value, i.e. an unset slot.
+	pub fn value_bytes(&self) -> Vec<u8> {
+		let mut value = [0u8; 32];
+		self.value.to_big_endian(&mut value);
+		value[self.value.leading_zeros() as usize / 8..].to_vec()
+	}
+
+	/// The `(proof, key, value)` arguments of `GovModule::verify_proof`.
+	pub fn verify_proof_args(&self) -> (Vec<Vec<u8>>, Vec<u8>, U256) {
+		(self.proof.clone(), self.key_bytes(), self.value)
+	}
+}
+
+// Proof nodes are 0x-prefixed hex strings.
+fn deserialize_nodes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error> {
+	Vec::<String>::deserialize(deserializer)?
+		.iter()
+		.map(|node| hex::decode(node.trim_start_matches("0x")).map_err(D::Error::custom))
+		.collect()
+}
@@ -0,0 +1,51 @@
+//! Storage slots of Solidity state variables, which are the keys of the storage trie of a contract.
+//! See the Solidity documentation on the layout of state variables in storage.
+
+use primitive_types::{H160, H256, U256};
+
+use crate::keccak;
+
+/// The slot of the state variable declared at position `index` of the contract layout.
+pub fn slot(index: u64) -> H256 {
+	uint_key(index.into())
+}
+
+/// An address as a mapping key, left padded to 32 bytes.
+pub fn address_key(address: &H160) -> H256 {
+	(*address).into()
+}
+
+/// An unsigned integer as a mapping key.
+pub fn uint_key(value: U256) -> H256 {
+	let mut key = H256::zero();
+	value.to_big_endian(key.as_bytes_mut());
+	key
+}
+
+/// The slot of `mapping[key]` for the mapping at `slot`, where `key` is padded to 32 bytes.
+/// Nested mappings apply this once per key, starting with the outermost one.
+pub fn mapping_slot(slot: &H256, key: &H256) -> H256 {
+	let mut preimage = [0u8; 64];
+	preimage[..32].copy_from_slice(key.as_bytes());
+	preimage[32..].copy_from_slice(slot.as_bytes());
+	H256(keccak(&preimage))
+}
+
+/// The slot of `balances[holder]` for a `mapping(address => uint)` at `slot`.
+pub fn address_mapping_slot(slot: &H256, holder: &H160) -> H256 {
+	mapping_slot(slot, &address_key(holder))
+}
+
+/// The slot of `array[index]` for the dynamic array at `slot`, whose elements take `element_slots`
+/// slots each. The length of the array is stored at `slot` itself.
+pub fn array_element_slot(slot: &H256, index: U256, element_slots: U256) -> H256 {
+	let first = U256::from_big_endian(&keccak(slot.as_bytes()));
+	// Slots wrap around like EVM arithmetic.
+	let offset = index.overflowing_mul(element_slots).0;
+	uint_key(first.overflowing_add(offset).0)
+}
+
+/// The slot of the struct member `offset` slots after the first slot of the struct at `slot`.
+pub fn struct_member_slot(slot: &H256, offset: u64) -> H256 {
+	uint_key(U256::from_big_endian(slot.as_bytes()).overflowing_add(offset.into()).0)
+}
@@ -0,0 +1,666 @@
+use crate::*;
+use primitive_types::{H160, H256, U256};
+
+// Storage root, key, value and proof of an `eth_getProof` storage proof ending in an odd leaf node.
+fn odd_leaf_node_proof() -> (Vec<u8>, Vec<u8>, Vec<u8>, Vec<Vec<u8>>) {
+	let root = hex::decode("80c9a98e6d091d9870fa6e26f5d935dd6174a4564600e929011f682a825aa5b8").unwrap();
+	let key = hex::decode("ea0d43baabaa35779c32a65010508497328024f66e5d6d3246da6d5f1196e017").unwrap();
+	let value = hex::decode("029ade03ae443c0cf00000").unwrap();
+	let proof = vec![
+		hex::decode("f90211a0a5177e86acbc4cf377a71bb1eefc5a6fbc291bdaa24a1329fc0a7d8b1d1c1b6ea04617a3e6d77a766bf9765ea99f6551a608da5a3a92b6d3d4d77cd72c2956a691a089378dc01a14c46f4bc70b18f4f89ed999f662b10321be19f17f8a28f3ed628aa032b7aa9f61401e9dfa443173d009991ef1b11695b833b791ba955e2d201582a2a078a52bd9e3780cefaa22782c1d848d58f0c44e840be1aced5b27274e04532d44a07cc1d0bd92bf8787c419ee93c46b3081e9c327e67ca40efa12fc3340b6a57af3a0d0ed8ccb13e91933017f33bc981aa39203fdd13691c8c10ea4e1c7235b9828caa0c9aff81ec497dce19b1e11a4558b8029377bbc132ecfa78bc9f031d3b95d59c5a060258dfe689213ea4448a9a499fbad3000f28f9521a939bc1fb385b0eae28eaaa039fe2ef2f84b3e2b7c084352f2e525710232f715cdbf7a71ee17daa888e69770a0c6816a145bdb69b9e437cef45a5b8f9035b8dc53c8d9e477bd498c8f0efd21eca0c97e9000139384fde0b8c590c46e0525b73e9521dc4427fd2b47750a61dded9aa0d37b0cfb7bc7b900a79dee2d5f01e3c3fd2d788446bdccc44d44d76008c6f201a00423f3cfca18f91a52c67c6c69046fd74dadb9cf7715bfeb03ce4184d714b302a0dfa0947ef843dacb9518cbffd5d53271a3213043d88013e52620caea84123ddba054f8d0d47af761aefdce4ac8da11325d2d41fb068dc791e355933a3cac60212f80").unwrap(),
+		hex::decode("f90211a01333e6382d6c303e89efd56c58d6523e41cf5b1c5f793e9969907c6772d9ae10a0afc343d852a18b7422656be334a9e3722c06a8277ef7f2c3cae86debbf3409a3a095028816b3e724ee9639c84be71d53c1898f4c81561e55e108e63abaeb3ed737a0deba0cac9c82e14f7dc90e93a1304a819cb2a304d1425da719cfb8166c31c5d0a01b1846ed768d89ceac9308c858464e78643993eeff75b9bec7e69574b1365599a0eb29499beada326c068de2109b7e463bd66490563816fe61e6d756ad83c59ec3a0bbf1eb6861321dabfb10c6ac7815cb4b33024fc9600aaaa5baf2a90944e116f1a051c65b8a9ddf67f6258d9b5988211073d6bb88848398b35901d5dbc3620da72ea00e283db6762c7e4099d1263adaebbfaedbb774e78986730c9fd9234210edfd43a0f4a9080cb4c33f75ab48b6cd58d4c0e377f457e0d56554f751d1ff5c3601cfeda09e1735497f07982c62f9a0aaf9e3202b3dad70bca9a371b0019dc99ebc7ef142a04a4c289e7c414dc4d4480efb87310600a582f33db748545b1045a09edb731c5aa09b99c3ad4c76428956477af2ecf7433ad2450bcf6e60ca793c16f66267ec41c5a03279d8fa4088ae2835d1128869efde4fac55b3f7f74b068ec244dbf99e6420bda098f20887e26eff360071b46b608f7d693bb4a8eca3f734cfae84e8a88977bec0a0e16218d34192fa20fbece5799730cdf6c0870c17bf946f7c413743e05852cf8980").unwrap(),
+		hex::decode("f90211a0ac98c7316786c6533a57c8b79d460d196e21c9ea02454e70e1ccfde69965d7e3a00fe68c4d7055ba645b3d94966caf7f9c4566521b518cd4886e1b270d69dc09e0a001c22697980e8a2963dbc75d3d41fe599ee2ab59742b431547742ae7e3d72277a02e83b3c4cd7e541029235e73931a25aa96a9bcd5ee1dfc492d4167c2956b43f2a0d5c2d0732b5d73b7bdb0d253df3b7d16d22795c41ab0c12ef80b99f077f59d2ea02bb969bda9b3ff90cb5cd3dccfeb65c10796886f8d68aaafcc30a3f8b48a9769a0c93c2804966ede5ea560d9c14044d2224fda91d91884e296bfcc6e70f5b0283ea0ca9427e11890a4f01f5900305de7abe2e124f33d4db9468bb1b09621c7d755eea04fc1da0ce4f6a2a9a8b26c386011549d4f2f1b5d2ee45c049578fb1f34f6e9b0a0e4bd6053812407401a3f231e75059e0a639d73f7acdb7d3d1f51bcdcff5df140a066de3c171230a68b2b3b1355ee7e57054ebdd7208f5b6b4d3fecc89bcc78bc50a04a4304e1d8fc660c6901a9d40de4ac11b4b8eb1244349aa1344dd8f6c0cd9a8fa0c526cc5c77a725e726d1336ae57e0e9dafc55dfba1a5ba04edf9bc6bdf1b678aa07cfd650999781b5aa61b7def4734708bff88a39c427480a8cc977ff128eab694a0c5489fd491915a661eefbe2ca9908a1190e5ab82907cb60ba3f4a13f5fc1638ba0c47ab01dc431fd9e6cd1544e7dc7582eed2c6fba1fe8699736c43b5075865b6b80").unwrap(),
+		hex::decode("f901f1a0f69ccfc218fb33dbc09241b3fb73d7618abd73eedfe0d0c27ec38194cc49f155a0a02657b99dbd54f6a7822ffaea4ba4639c46280c5f1fc1c2dec99fae4fa5c812a056da2d7e78f685e285d6bef4c16c96f3386d4edd8cf4daf3265134e34ae38e99a037c19426b7a66c5c6243c4ca4ba6fec137b33397e12bf077a6989deb28e6723fa0f95de4d700c36c59c35533d8d45342bea2db6f260c64bba1753812d548bc2b1ca086b4b33ce01faa10a245e4fea3b44b9d7144995f4f56349c7e98253281b6c3d6a03e4dc6648e42d122098a9ee9d9c46e900307c0c2ac7f1c741dd25e6b384bcb8ba0ca505ef73025919758cc4757c4f93d99cee37765b8a3d1c0b7028e76e0c5d9d3a025688a7ccdddfc40229945106bed07696a7882f4e1376e25f09a8061c3ded292a01eeb996259136a38e6b5eed77096c1a32372380bbf27e1b3d2177c64807c56eca08883d51217a7484c4d0673e501cc9da2046918842b26b3ef6bb0701fc2ca53b8a0a87ef4ccc71e14c850b1fd40cb5385bf939e24f02b97ad70d930347f46ed1dfca038ec805afcaffb37cc18ad86497172421f5dec53630fc3a5be68cb3e6d5df12b80a0e310547f945c43c56a886a8ae5ed81f5a456dd3282a5bfb4c5449ac279f0d54ca09726c103c0a37fa3e6838fed6697b711957028896b0736dd80fb178dcd69bed380").unwrap(),
+		hex::decode("f8918080a0929cda5a139acd2d8ecb7f4bb82df6e44e7d809942017179b276cee8467f0a0680a0a08c3ce65d23544d70d19893b1b6e1308e570d3a2be2a7d23aa4fe90a4dfd0cc8080a0117d1d285a0c40e29efb210f3cbc08f87e11094f91b0b85545a6dddd1a1ecd7e808080808080a0f553c1cecdbcad6265d17a07e7a00dc05af7e6b08857d773dc92342340b81b928080").unwrap(),
+		hex::decode("ec9e3b6e082fdebda47dc55282be5e6b0140a1580b3341ea097f15c13ea588728c8b029ade03ae443c0cf00000").unwrap()
+	];
+	(root, key, value, proof)
+}
+
+#[test]
+fn verifies_proof_with_odd_leaf_node() {
+	let (root, key, value, proof) = odd_leaf_node_proof();
+	assert_eq!(verify_merkle_proof(&root, &proof, &keccak(&key), &value), Ok(()));
+}
+
+#[test]
+fn reports_index_of_failing_node() {
+	let (root, key, value, mut proof) = odd_leaf_node_proof();
+	assert_eq!(verify_merkle_proof(&root[1..], &proof, &keccak(&key), &value), Err(ProofError::HashMismatch(0)));
+	assert_eq!(verify_merkle_proof(&root, &proof, &keccak(&key), &value[1..]), Err(ProofError::ValueMismatch(5)));
+	// A different key leads to a child of the root node that is not in the proof.
+	assert_eq!(verify_merkle_proof(&root, &proof, &keccak(&[0u8]), &value), Err(ProofError::HashMismatch(1)));
+	proof.pop();
+	assert_eq!(verify_merkle_proof(&root, &proof, &keccak(&key), &value), Err(ProofError::MissingNode(5)));
+}
+
+#[test]
+fn rejects_unused_trailing_nodes() {
+	let (root, key, value, mut proof) = odd_leaf_node_proof();
+	let key = keccak(&key);
+	let leaf_value = hex::decode("8b029ade03ae443c0cf00000").unwrap();
+	proof.push(proof[5].clone());
+	assert_eq!(verify_merkle_proof(&root, &proof, &key, &value), Err(ProofError::UnusedNodes(6)));
+	assert_eq!(read_storage_value(&root, &proof, &key), Err(ProofError::UnusedNodes(6)));
+	assert_eq!(read_proof(&root, &proof, &key, ProofMode::Strict), Err(ProofError::UnusedNodes(6)));
+	assert_eq!(read_proof(&root, &proof, &key, ProofMode::Lenient), Ok(Some(&leaf_value[..])));
+
+	// Nodes after the node showing the absence of a key are unused as well.
+	let mut absent = key;
+	absent[31] ^= 1;
+	assert_eq!(verify_merkle_proof(&root, &proof, &absent, &[]), Err(ProofError::UnusedNodes(6)));
+	assert_eq!(read_proof(&root, &proof, &absent, ProofMode::Lenient), Ok(None));
+
+	let entries = state_trie_entries();
+	let state_root = H256::from_slice(&trie::root(&entries));
+	let mut account_proof = trie::prove(&entries, &keccak(TOKEN.as_bytes()));
+	account_proof.push(account_proof[0].clone());
+	assert_eq!(
+		verify_account_proof(&state_root, &account_proof, &TOKEN),
+		Err(ProofError::UnusedNodes(account_proof.len() - 1))
+	);
+}
+
+#[test]
+fn verifies_multi_proofs() {
+	let entries: Vec<(Vec<u8>, Vec<u8>)> = (1..=50u8)
+		.map(|i| (keccak(&[i]).to_vec(), rlp::encode(&vec![i; 20]).to_vec()))
+		.collect();
+	let root = trie::root(&entries);
+	let present: Vec<u8> = vec![3, 7, 20, 41];
+	let mut items: Vec<(Vec<u8>, Vec<u8>)> = present.iter().map(|&i| (keccak(&[i]).to_vec(), vec![i; 20])).collect();
+	// An absent key with an empty value.
+	items.push((keccak(&[0]).to_vec(), vec![]));
+
+	// The proofs share the root node, which is given once. The order of the nodes does not matter.
+	let proofs: Vec<Vec<Vec<u8>>> = items.iter().map(|(key, _)| trie::prove(&entries, key)).collect();
+	let mut nodes: Vec<Vec<u8>> = vec![];
+	for node in proofs.iter().flatten().rev() {
+		if !nodes.contains(node) {
+			nodes.push(node.clone());
+		}
+	}
+	assert!(nodes.len() < proofs.iter().map(Vec::len).sum::<usize>());
+	assert_eq!(verify_multi_proof(&root, &nodes, &items), Ok(()));
+	assert_eq!(verify_multi_proof(&root, &nodes[..], &items[..0]), Err(ProofError::UnusedNodes(0)));
+
+	let root_index = nodes.iter().position(|node| keccak(node)[..] == root[..]).unwrap();
+	let mut wrong_items = items.clone();
+	wrong_items[0].1 = vec![0xff; 20];
+	let leaf_index = nodes.iter().position(|node| *node == proofs[0][proofs[0].len() - 1]).unwrap();
+	assert_eq!(verify_multi_proof(&root, &nodes, &wrong_items), Err(ProofError::ValueMismatch(leaf_index)));
+	let mut wrong_items = items.clone();
+	wrong_items[4].1 = vec![0xff; 20];
+	assert!(matches!(verify_multi_proof(&root, &nodes, &wrong_items), Err(ProofError::KeyMismatch(_))));
+
+	// Every node must be used once.
+	let mut duplicated = nodes.clone();
+	duplicated.push(nodes[root_index].clone());
+	assert!(matches!(verify_multi_proof(&root, &duplicated, &items), Err(ProofError::UnusedNodes(_))));
+	let mut unused = nodes.clone();
+	let other_proof = trie::prove(&entries, &keccak(&[30]));
+	unused.push(other_proof[other_proof.len() - 1].clone());
+	assert_eq!(verify_multi_proof(&root, &unused, &items), Err(ProofError::UnusedNodes(nodes.len())));
+	let mut missing = nodes.clone();
+	missing.remove(leaf_index);
+	assert_eq!(verify_multi_proof(&root, &missing, &items), Err(ProofError::MissingNode(missing.len())));
+	assert_eq!(verify_multi_proof(&root[1..], &nodes, &items), Err(ProofError::MissingNode(nodes.len())));
+
+	// A single proof is a node set as well.
+	let (root, key, value, proof) = odd_leaf_node_proof();
+	assert_eq!(verify_multi_proof(&root, &proof, &[(keccak(&key).to_vec(), value)]), Ok(()));
+}
+
+#[test]
+fn verifies_absence() {
+	let (root, key, _, proof) = odd_leaf_node_proof();
+	let key = keccak(&key);
+	assert_eq!(verify_absence(&root, &proof, &key), Err(ProofError::KeyPresent(5)));
+
+	// A branch node of the storage trie without a child for the fifth key nibble.
+	let children = match Node::decode(&proof[4]).unwrap() {
+		Node::Branch { children, .. } => children,
+		node => panic!("expected branch node, got {:?}", node),
+	};
+	let empty_slot = children.iter().position(|child| child.is_empty()).unwrap() as u8;
+	let mut absent = key;
+	absent[2] = empty_slot << 4 | (absent[2] & 0x0f);
+	assert_eq!(verify_absence(&root, &proof[..5], &absent), Ok(()));
+	// The proof must end at the node showing the absence.
+	assert_eq!(verify_absence(&root, &proof, &absent), Err(ProofError::UnusedNodes(5)));
+	assert_eq!(verify_absence(&root, &proof[..4], &absent), Err(ProofError::MissingNode(4)));
+
+	// A leaf node whose path differs from the remaining key nibbles.
+	let mut absent = key;
+	absent[31] ^= 1;
+	assert_eq!(verify_absence(&root, &proof, &absent), Ok(()));
+
+	// An extension node whose path diverges from the key.
+	let entries: Vec<(Vec<u8>, Vec<u8>)> = vec![
+		(vec![0x12, 0x34], rlp::encode(&vec![0xaa; 40]).to_vec()),
+		(vec![0x12, 0x56], rlp::encode(&vec![0xbb; 40]).to_vec()),
+	];
+	let root = trie::root(&entries);
+	let proof = trie::prove(&entries, &[0x13, 0x34]);
+	assert!(matches!(Node::decode(&proof[0]), Ok(Node::Extension { .. })));
+	assert_eq!(verify_absence(&root, &proof, &[0x13, 0x34]), Ok(()));
+	assert_eq!(verify_absence(&root, &trie::prove(&entries, &[0x12, 0x34]), &[0x12, 0x34]), Err(ProofError::KeyPresent(2)));
+}
+
+#[test]
+fn follows_embedded_nodes() {
+	// With short keys and values, nodes shorter than 32 bytes are embedded in their parent instead of
+	// being part of the proof.
+	let entries: Vec<(Vec<u8>, Vec<u8>)> = vec![
+		(vec![0x11], rlp::encode(&1u8).to_vec()),
+		(vec![0x12], rlp::encode(&2u8).to_vec()),
+		(vec![0x21, 0x00], rlp::encode(&vec![0xaa; 40]).to_vec()),
+		(vec![0x21, 0x01], rlp::encode(&3u8).to_vec()),
+	];
+	let root = trie::root(&entries);
+
+	// A branch node and its leaf nodes embedded in the root node.
+	let proof = trie::prove(&entries, &[0x12]);
+	assert_eq!(proof.len(), 1);
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x12], &[2]), Ok(()));
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x12], &[1]), Err(ProofError::ValueMismatch(0)));
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x13], &[]), Ok(()));
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x13], &[1]), Err(ProofError::KeyMismatch(0)));
+
+	// A leaf node embedded in a hashed branch node below an extension node.
+	let proof = trie::prove(&entries, &[0x21, 0x01]);
+	assert_eq!(proof.len(), 3);
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x21, 0x01], &[3]), Ok(()));
+	let proof = trie::prove(&entries, &[0x21, 0x00]);
+	assert_eq!(proof.len(), 4);
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x21, 0x00], &[0xaa; 40]), Ok(()));
+
+	// Embedded nodes are covered by the hash of the node holding them.
+	let mut tampered = trie::prove(&entries, &[0x12]);
+	let position = tampered[0].iter().position(|&byte| byte == 0x02).unwrap();
+	tampered[0][position] = 0x05;
+	assert_eq!(verify_merkle_proof(&root, &tampered, &[0x12], &[5]), Err(ProofError::HashMismatch(0)));
+}
+
+#[test]
+fn reads_storage_values() {
+	let (root, key, _, proof) = odd_leaf_node_proof();
+	let value = U256::from(0x029ade03ae443c0cf00000u128);
+	assert_eq!(read_storage_value(&root, &proof, &keccak(&key)), Ok(value));
+	assert_eq!(verify_storage_value(&root, &proof, &keccak(&key), value), Ok(()));
+	assert_eq!(verify_storage_value(&root, &proof, &keccak(&key), value + 1), Err(ProofError::ValueMismatch(5)));
+
+	// Unset slots are not in the trie and read as zero.
+	let entries: Vec<(Vec<u8>, Vec<u8>)> = (1..=20u8)
+		.map(|i| (keccak(&[i]).to_vec(), rlp::encode(&vec![0, i]).to_vec()))
+		.collect();
+	let root = trie::root(&entries);
+	let absent = keccak(&[0]);
+	let proof = trie::prove(&entries, &absent);
+	assert_eq!(read_storage_value(&root, &proof, &absent), Ok(U256::zero()));
+	assert_eq!(verify_storage_value(&root, &proof, &absent, U256::zero()), Ok(()));
+	assert!(matches!(verify_storage_value(&root, &proof, &absent, U256::one()), Err(ProofError::KeyMismatch(_))));
+	// Leading zero bytes do not change the value.
+	let proof = trie::prove(&entries, &keccak(&[7]));
+	assert_eq!(read_storage_value(&root, &proof, &keccak(&[7])), Ok(U256::from(7)));
+}
+
+#[test]
+fn reads_values_stored_in_branch_nodes() {
+	// Keys of different lengths, where the value of a key that is a prefix of other keys is stored in
+	// the value slot of a branch node.
+	let long_value = rlp::encode(&vec![0xbb; 40]).to_vec();
+	let entries: Vec<(Vec<u8>, Vec<u8>)> = vec![
+		(vec![0x12], long_value.clone()),
+		(vec![0x12, 0x34], rlp::encode(&vec![0xcc; 40]).to_vec()),
+		(vec![0x12, 0x56], rlp::encode(&vec![0xdd; 40]).to_vec()),
+		(vec![0x78], rlp::encode(&vec![0xee; 40]).to_vec()),
+	];
+	let root = trie::root(&entries);
+	let proof = trie::prove(&entries, &[0x12]);
+	let last = proof.len() - 1;
+	assert!(matches!(Node::decode(&proof[last]), Ok(Node::Branch { .. })));
+	assert_eq!(read_proof(&root, &proof, &[0x12], ProofMode::Strict), Ok(Some(&long_value[..])));
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x12], &[0xbb; 40]), Ok(()));
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x12], &[0xcc; 40]), Err(ProofError::ValueMismatch(last)));
+	// Keys continuing past the branch node are proven through its children.
+	let proof = trie::prove(&entries, &[0x12, 0x34]);
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x12, 0x34], &[0xcc; 40]), Ok(()));
+
+	// A branch node with an empty value slot proves that the key ending at it is not in the trie.
+	let entries = &entries[1..];
+	let root = trie::root(entries);
+	let proof = trie::prove(entries, &[0x12]);
+	assert_eq!(read_proof(&root, &proof, &[0x12], ProofMode::Strict), Ok(None));
+	assert_eq!(verify_merkle_proof(&root, &proof, &[0x12], &[]), Ok(()));
+	assert_eq!(
+		verify_merkle_proof(&root, &proof, &[0x12], &[0xbb; 40]),
+		Err(ProofError::KeyMismatch(proof.len() - 1))
+	);
+}
+
+#[test]
+fn verifies_raw_values_of_transaction_tries() {
+	// Like a transaction trie: keys are RLP encoded indices, of one or two bytes, and values are RLP lists.
+	let transaction = |index: u64| {
+		let mut stream = rlp::RlpStream::new_list(3);
+		stream.append(&index).append(&vec![0xaa; 20]).append(&vec![0xbb; 20]);
+		stream.out().to_vec()
+	};
+	let entries: Vec<(Vec<u8>, Vec<u8>)> = (0..130u64)
+		.map(|index| (rlp::encode(&index).to_vec(), transaction(index)))
+		.collect();
+	let root = trie::root(&entries);
+
+	for index in &[0u64, 1, 127, 128, 129] {
+		let key = rlp::encode(index).to_vec();
+		let proof = trie::prove(&entries, &key);
+		let last = proof.len() - 1;
+		assert_eq!(read_proof(&root, &proof, &key, ProofMode::Strict), Ok(Some(&transaction(*index)[..])));
+		assert_eq!(verify_raw_value(&root, &proof, &key, &transaction(*index)), Ok(()));
+		assert_eq!(verify_raw_value(&root, &proof, &key, &transaction(index + 1)), Err(ProofError::ValueMismatch(last)));
+		// RLP lists are not byte strings, so `verify_merkle_proof` cannot verify them.
+		assert_eq!(
+			verify_merkle_proof(&root, &proof, &key, &transaction(*index)),
+			Err(ProofError::ValueMismatch(last))
+		);
+	}
+
+	let absent = rlp::encode(&200u64).to_vec();
+	let proof = trie::prove(&entries, &absent);
+	assert_eq!(verify_raw_value(&root, &proof, &absent, &[]), Ok(()));
+	assert_eq!(
+		verify_raw_value(&root, &proof, &absent, &transaction(200)),
+		Err(ProofError::KeyMismatch(proof.len() - 1))
+	);
+}
+
+#[test]
+fn decodes_compact_paths() {
+	let (path, is_leaf) = decode_compact_path(&[0x00, 0xab]).unwrap();
+	assert_eq!((path, is_leaf), (NibbleSlice::new(&[0xab]), false));
+	let (path, is_leaf) = decode_compact_path(&[0x1a, 0xbc]).unwrap();
+	assert_eq!((path, is_leaf), (NibbleSlice::new_offset(&[0x0a, 0xbc], 1), false));
+	let (path, is_leaf) = decode_compact_path(&[0x20]).unwrap();
+	assert!(path.is_empty() && is_leaf);
+	let (path, is_leaf) = decode_compact_path(&[0x3f]).unwrap();
+	assert_eq!((path.len(), path.at(0), is_leaf), (1, 0xf, true));
+	// Unknown prefix, non-zero padding nibble and missing prefix.
+	assert!(decode_compact_path(&[0x40]).is_none());
+	assert!(decode_compact_path(&[0x01]).is_none());
+	assert!(decode_compact_path(&[]).is_none());
+}
+
+#[test]
+fn decodes_nodes() {
+	let (_, _, _, proof) = odd_leaf_node_proof();
+	match Node::decode(&proof[4]).unwrap() {
+		Node::Branch { children, value } => {
+			assert_eq!(children.iter().filter(|child| !child.is_empty()).count(), 4);
+			assert!(value.is_empty());
+		},
+		node => panic!("expected branch node, got {:?}", node),
+	}
+	match Node::decode(&proof[5]).unwrap() {
+		Node::Leaf { path, value } => {
+			assert_eq!(path.len(), 59);
+			assert_eq!(value, &hex::decode("8b029ade03ae443c0cf00000").unwrap()[..]);
+		},
+		node => panic!("expected leaf node, got {:?}", node),
+	}
+	// Extension nodes share at least one nibble with their child.
+	assert_eq!(Node::decode(&hex::decode("e200a0f35b68d9f156ea05fd7143c5bdfef9a1ab1cadc6fa2ed36bf2da0b1f76e225af").unwrap()), Err(NodeError::InvalidPrefix));
+	assert_eq!(Node::decode(&hex::decode("c3010203").unwrap()), Err(NodeError::InvalidRlp));
+	assert_eq!(Node::decode(&hex::decode("8180").unwrap()), Err(NodeError::InvalidRlp));
+	// Child references are 32 byte hashes, or embedded nodes shorter than 32 bytes.
+	let mut stream = rlp::RlpStream::new_list(2);
+	stream.append(&vec![0x11u8]).begin_list(2).append(&vec![0x20u8]).append(&vec![0x01u8]);
+	assert!(matches!(Node::decode(&stream.out()), Ok(Node::Extension { .. })));
+	let mut stream = rlp::RlpStream::new_list(2);
+	stream.append(&vec![0x11u8]).append(&vec![0u8; 31]);
+	assert_eq!(Node::decode(&stream.out()), Err(NodeError::InvalidRlp));
+	let mut stream = rlp::RlpStream::new_list(2);
+	stream.append(&vec![0x11u8]).begin_list(2).append(&vec![0x20u8]).append(&vec![0u8; 32]);
+	assert_eq!(Node::decode(&stream.out()), Err(NodeError::InvalidRlp));
+}
+
+// Address of the token contract in the generated state tries.
+const TOKEN: H160 = H160([0xc0, 0x0e, 0x94, 0xcb, 0x66, 0x2c, 0x35, 0x20, 0x28, 0x2e, 0x6f, 0x57, 0x17, 0x21, 0x40, 0x04, 0xa7, 0xf2, 0x68, 0x88]);
+
+fn token_account() -> Account {
+	let (storage_root, _, _, _) = odd_leaf_node_proof();
+	Account {
+		nonce: U256::one(),
+		balance: U256::zero(),
+		storage_root: H256::from_slice(&storage_root),
+		code_hash: H256::repeat_byte(0xcc),
+	}
+}
+
+// State trie entries of the token contract and 200 externally owned accounts.
+fn state_trie_entries() -> Vec<(Vec<u8>, Vec<u8>)> {
+	let mut entries: Vec<(Vec<u8>, Vec<u8>)> = (1..=200u64).map(|i| {
+		let account = Account {
+			nonce: U256::from(i),
+			balance: U256::exp10(18) * i,
+			storage_root: H256::from_slice(&keccak(&rlp::NULL_RLP)),
+			code_hash: H256::from_slice(&keccak(&[])),
+		};
+		(keccak(H160::from_low_u64_be(i).as_bytes()).to_vec(), rlp::encode(&account).to_vec())
+	}).collect();
+	entries.push((keccak(TOKEN.as_bytes()).to_vec(), rlp::encode(&token_account()).to_vec()));
+	entries
+}
+
+#[test]
+fn verifies_account_proof() {
+	let entries = state_trie_entries();
+	let state_root = H256::from_slice(&trie::root(&entries));
+	let proof = trie::prove(&entries, &keccak(TOKEN.as_bytes()));
+	assert_eq!(verify_account_proof(&state_root, &proof, &TOKEN), Ok(Some(token_account())));
+}
+
+#[test]
+fn verifies_account_proof_of_missing_account() {
+	let entries = state_trie_entries();
+	let state_root = H256::from_slice(&trie::root(&entries));
+	let missing = H160::repeat_byte(0x11);
+	let proof = trie::prove(&entries, &keccak(missing.as_bytes()));
+	assert_eq!(verify_account_proof(&state_root, &proof, &missing), Ok(None));
+}
+
+#[test]
+fn rejects_account_proof_against_wrong_state_root() {
+	let entries = state_trie_entries();
+	let proof = trie::prove(&entries, &keccak(TOKEN.as_bytes()));
+	assert_eq!(verify_account_proof(&H256::repeat_byte(1), &proof, &TOKEN), Err(ProofError::HashMismatch(0)));
+	// The proof of another account does not prove the token contract.
+	let state_root = H256::from_slice(&trie::root(&entries));
+	let other_proof = trie::prove(&entries, &keccak(H160::from_low_u64_be(1).as_bytes()));
+	assert!(verify_account_proof(&state_root, &other_proof, &TOKEN) != Ok(Some(token_account())));
+}
+
+// RLP encoded post-London block header with the given state root.
+fn rlp_header(number: u64, state_root: &[u8]) -> Vec<u8> {
+	let mut stream = rlp::RlpStream::new_list(16);
+	stream.append(&H256::repeat_byte(0x01)) // parent hash
+		.append(&H256::repeat_byte(0x02)) // ommers hash
+		.append(&H160::repeat_byte(0x03)) // beneficiary
+		.append(&state_root)
+		.append(&H256::repeat_byte(0x04)) // transactions root
+		.append(&H256::repeat_byte(0x05)) // receipts root
+		.append(&vec![0u8; 256]) // logs bloom
+		.append(&U256::from(9_000_000_000_000_000u64)) // difficulty
+		.append(&number)
+		.append(&30_000_000u64) // gas limit
+		.append(&15_000_000u64) // gas used
+		.append(&1_630_000_000u64) // timestamp
+		.append(&b"extra".to_vec())
+		.append(&H256::repeat_byte(0x06)) // mix hash
+		.append(&vec![0u8; 8]) // nonce
+		.append(&50_000_000_000u64); // base fee
+	stream.out().to_vec()
+}
+
+#[test]
+fn decodes_header() {
+	let state_root = trie::root(&state_trie_entries());
+	let encoded = rlp_header(13084960, &state_root);
+	let header = Header::decode(&encoded).unwrap();
+	assert_eq!(header.hash, H256(keccak(&encoded)));
+	assert_eq!(header.parent_hash, H256::repeat_byte(0x01));
+	assert_eq!(header.state_root.as_bytes(), &state_root[..]);
+	assert_eq!(header.transactions_root, H256::repeat_byte(0x04));
+	assert_eq!(header.receipts_root, H256::repeat_byte(0x05));
+	assert_eq!((header.number, header.timestamp), (13084960, 1_630_000_000));
+
+	// Truncated headers, trailing bytes and lists with too few fields are rejected.
+	assert!(Header::decode(&encoded[..encoded.len() - 1]).is_err());
+	assert!(Header::decode(&[&encoded[..], &[0x80]].concat()).is_err());
+	assert!(Header::decode(&rlp::encode_list::<Vec<u8>, _>(&vec![vec![1u8]; 14])).is_err());
+}
+
+#[test]
+fn computes_storage_slots() {
+	let hash = |hex: &str| H256::from_slice(&hex::decode(hex).unwrap());
+	// keccak(pad(0) ++ pad(0)) and keccak(pad(0)).
+	assert_eq!(mapping_slot(&slot(0), &uint_key(U256::zero())), hash("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"));
+	assert_eq!(array_element_slot(&slot(0), U256::zero(), U256::one()), hash("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"));
+	assert_eq!(array_element_slot(&slot(0), U256::from(3), U256::from(2)), hash("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e569"));
+	assert_eq!(struct_member_slot(&slot(0), 2), slot(2));
+	// Struct members after the last slot wrap around.
+	assert_eq!(struct_member_slot(&H256::repeat_byte(0xff), 1), slot(0));
+
+	let holder = H160::from_slice(&hex::decode("17c5185167401ed00cf5f5b2fc97d9bbfdb7d025").unwrap());
+	let balance_slot = hash("c5520034c9bad690d1918def072edbac4a4ae91e3e6f4984281dd354d26f8aa6");
+	assert_eq!(address_key(&holder).as_bytes()[12..], *holder.as_bytes());
+	assert_eq!(address_mapping_slot(&slot(1), &holder), balance_slot);
+	// `allowances[holder][spender]` of a `mapping(address => mapping(address => uint))`.
+	let spender = H160::repeat_byte(0x01);
+	assert_eq!(
+		address_mapping_slot(&address_mapping_slot(&slot(0), &holder), &spender),
+		mapping_slot(&mapping_slot(&slot(0), &address_key(&holder)), &address_key(&spender))
+	);
+}
+
+#[test]
+fn parses_eth_get_proof_response() {
+	let (storage_root, key, value, proof) = odd_leaf_node_proof();
+	let account_proof = trie::prove(&state_trie_entries(), &keccak(TOKEN.as_bytes()));
+	let hex_list = |nodes: &[Vec<u8>]| nodes.iter().map(|node| format!("\"0x{}\"", hex::encode(node))).collect::<Vec<_>>().join(",");
+	let json = format!(
+		r#"{{"address":"0x{}","accountProof":[{}],"balance":"0x0","codeHash":"0x{}","nonce":"0x1","storageHash":"0x{}",
+		"storageProof":[{{"key":"0x{}","value":"0x29ade03ae443c0cf00000","proof":[{}]}}]}}"#,
+		hex::encode(TOKEN), hex_list(&account_proof), hex::encode(token_account().code_hash), hex::encode(&storage_root),
+		hex::encode(&key), hex_list(&proof),
+	);
+
+	let response = rpc::ProofResponse::from_json(&json).unwrap();
+	assert_eq!(response.address, TOKEN);
+	assert_eq!(response.account_proof, account_proof);
+	assert_eq!(response.nonce, U256::one());
+	assert_eq!(response.storage_hash.as_bytes(), &storage_root[..]);
+	assert_eq!(response.storage_proof[0].value_bytes(), value);
+	assert_eq!(response.storage_proof[0].verify_proof_args(), (proof, key, U256::from_big_endian(&value)));
+
+	// Short keys are left padded to 32 bytes and zero values are empty.
+	let storage_proof = rpc::StorageProof { key: U256::from(3), value: U256::zero(), proof: vec![] };
+	assert_eq!(storage_proof.key_bytes(), H256::from_low_u64_be(3).as_bytes());
+	assert!(storage_proof.value_bytes().is_empty());
+	assert!(rpc::ProofResponse::from_json("{}").is_err());
+}
+
+// Small deterministic xorshift generator so the malformed proof tests are reproducible.
+struct XorShift(u64);
+
+impl XorShift {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	fn below(&mut self, bound: usize) -> usize {
+		(self.next() % bound as u64) as usize
+	}
+
+	fn bytes(&mut self, len: usize) -> Vec<u8> {
+		(0..len).map(|_| self.next() as u8).collect()
+	}
+
+	// A random 2 or 17 item RLP list whose child reference at `slot` is `child`.
+	fn node(&mut self, child: Vec<u8>) -> Vec<u8> {
+		let mut items: Vec<Vec<u8>> = if self.below(2) == 0 {
+			(0..17).map(|_| { let len = [0, 1, 32][self.below(3)]; self.bytes(len) }).collect()
+		} else {
+			let path_len = self.below(34);
+			let mut path = self.bytes(path_len);
+			if let Some(first) = path.first_mut() { *first &= 0x3f; }
+			let value_len = self.below(40) + 1;
+			vec![path, self.bytes(value_len)]
+		};
+		let slot = self.below(items.len());
+		items[slot] = child;
+		rlp::encode_list::<Vec<u8>, _>(&items).to_vec()
+	}
+}
+
+#[test]
+fn verifier_rejects_malformed_proofs_without_panicking() {
+	let (_, key, value, proof) = odd_leaf_node_proof();
+	let hashed_key = keccak(&key);
+	let keys: Vec<&[u8]> = vec![&hashed_key[..], &[], &hashed_key[..3], &[0xff; 40]];
+	let mut rng = XorShift(0x2545f4914f6cdd1d);
+
+	// Truncate and corrupt each node of a valid proof, then make the result the root of the proof
+	// so that it passes the hash check and gets decoded.
+	for (index, node) in proof.iter().enumerate() {
+		let mut candidates: Vec<Vec<u8>> = (0..node.len()).map(|len| node[..len].to_vec()).collect();
+		for _ in 0..64 {
+			let mut candidate = node.clone();
+			let byte = rng.below(candidate.len());
+			candidate[byte] ^= 1 << rng.below(8);
+			candidates.push(candidate);
+		}
+		for candidate in candidates {
+			let root = keccak(&candidate);
+			let mut malformed_proof = vec![candidate];
+			malformed_proof.extend(proof[index + 1..].iter().cloned());
+			for key in &keys {
+				let _ = verify_merkle_proof(&root, &malformed_proof, key, &value);
+			}
+		}
+	}
+
+	// Chains of random nodes where every node references the hash of the next one.
+	for _ in 0..2000 {
+		let depth = rng.below(6) + 1;
+		let leaf_len = rng.below(80);
+		let mut malformed_proof = vec![rng.bytes(leaf_len)];
+		for _ in 1..depth {
+			let child = keccak(&malformed_proof[0]).to_vec();
+			malformed_proof.insert(0, rng.node(child));
+		}
+		let root = keccak(&malformed_proof[0]);
+		for key in &keys {
+			let _ = verify_merkle_proof(&root, &malformed_proof, key, &value);
+			let _ = verify_merkle_proof(&root, &malformed_proof, key, &[]);
+		}
+	}
+}
+
+// Minimal in-memory Merkle-Patricia trie used to generate proofs for tests.
+mod trie {
+	use crate::keccak;
+	use rlp::RlpStream;
+
+	pub type Entry = (Vec<u8>, Vec<u8>);
+
+	fn nibbles(key: &[u8]) -> Vec<u8> {
+		key.iter().flat_map(|byte| vec![byte >> 4, byte & 0x0f]).collect()
+	}
+
+	fn compact(path: &[u8], is_leaf: bool) -> Vec<u8> {
+		let flag = if is_leaf { 2 } else { 0 };
+		let mut padded = if path.len() % 2 == 1 { vec![flag + 1] } else { vec![flag, 0] };
+		padded.extend_from_slice(path);
+		padded.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect()
+	}
+
+	enum Kind<'a> {
+		Leaf(&'a [u8], &'a [u8]),
+		Extension(usize),
+		Branch,
+	}
+
+	// Entries are (key nibbles, value) pairs sharing the first `depth` nibbles.
+	fn kind(entries: &[Entry], depth: usize) -> Kind<'_> {
+		if entries.len() == 1 {
+			return Kind::Leaf(&entries[0].0[depth..], &entries[0].1);
+		}
+		let first = &entries[0].0;
+		let shared = (depth..first.len())
+			.take_while(|&i| entries.iter().all(|(key, _)| key.len() > i && key[i] == first[i]))
+			.count();
+		if shared > 0 { Kind::Extension(shared) } else { Kind::Branch }
+	}
+
+	fn append_reference(stream: &mut RlpStream, node: &[u8]) {
+		// Nodes shorter than 32 bytes are embedded in their parent.
+		if node.len() < 32 { stream.append_raw(node, 1); } else { stream.append(&keccak(node).to_vec()); }
+	}
+
+	fn child_entries(entries: &[Entry], depth: usize, nibble: u8) -> Vec<Entry> {
+		entries.iter().filter(|(key, _)| key.len() > depth && key[depth] == nibble).cloned().collect()
+	}
+
+	fn encode(entries: &[Entry], depth: usize) -> Vec<u8> {
+		let mut stream = RlpStream::new();
+		match kind(entries, depth) {
+			Kind::Leaf(path, value) => {
+				stream.begin_list(2).append(&compact(path, true)).append(&value.to_vec());
+			},
+			Kind::Extension(shared) => {
+				stream.begin_list(2).append(&compact(&entries[0].0[depth..depth + shared], false));
+				append_reference(&mut stream, &encode(entries, depth + shared));
+			},
+			Kind::Branch => {
+				stream.begin_list(17);
+				for nibble in 0..16 {
+					let children = child_entries(entries, depth, nibble);
+					if children.is_empty() { stream.append_empty_data(); } else { append_reference(&mut stream, &encode(&children, depth + 1)); }
+				}
+				match entries.iter().find(|(key, _)| key.len() == depth) {
+					Some((_, value)) => stream.append(value),
+					None => stream.append_empty_data(),
+				};
+			},
+		}
+		stream.out().to_vec()
+	}
+
+	fn prepare(entries: &[(Vec<u8>, Vec<u8>)]) -> Vec<Entry> {
+		let mut entries: Vec<Entry> = entries.iter().map(|(key, value)| (nibbles(key), value.clone())).collect();
+		entries.sort();
+		entries
+	}
+
+	/// Root hash of the trie holding `entries`.
+	pub fn root(entries: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+		keccak(&encode(&prepare(entries), 0)).to_vec()
+	}
+
+	/// Proof of `key` in the trie holding `entries`: the root node and all hashed nodes on the path to the key.
+	pub fn prove(entries: &[(Vec<u8>, Vec<u8>)], key: &[u8]) -> Vec<Vec<u8>> {
+		let key = nibbles(key);
+		let mut entries = prepare(entries);
+		let mut depth = 0;
+		let mut proof = vec![];
+		loop {
+			let node = encode(&entries, depth);
+			if depth == 0 || node.len() >= 32 {
+				proof.push(node);
+			}
+			match kind(&entries, depth) {
+				Kind::Leaf(..) => return proof,
+				Kind::Extension(shared) => {
+					if key.len() < depth + shared || key[depth..depth + shared] != entries[0].0[depth..depth + shared] {
+						return proof;
+					}
+					depth += shared;
+				},
+				Kind::Branch => {
+					if key.len() == depth {
+						return proof;
+					}
+					entries = child_entries(&entries, depth, key[depth]);
+					if entries.is_empty() {
+						return proof;
+					}
+					depth += 1;
+				},
+			}
+		}
+	}
+}
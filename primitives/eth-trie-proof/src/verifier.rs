@@ -0,0 +1,308 @@
+use primitive_types::U256;
+use rlp::Rlp;
+
+use crate::{keccak, NibbleSlice, Node, NodeError};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Reason a merkle proof failed verification. Each variant carries the index of the proof node
+/// at which verification failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofError {
+	/// The hash of the node does not match the expected root (index 0) or the reference stored in its parent node.
+	HashMismatch(usize),
+	/// The node is not a valid RLP list.
+	InvalidRlp(usize),
+	/// The key is not in the trie but a value was expected: the path stored in a leaf or extension node
+	/// diverges from the remaining key nibbles, or a branch node has no child for the next key nibble.
+	KeyMismatch(usize),
+	/// The value stored in the trie does not match the expected value.
+	ValueMismatch(usize),
+	/// The compact encoded path of a leaf or extension node has an unknown prefix.
+	InvalidPrefix(usize),
+	/// The proof ended before reaching the node referenced by the previous node.
+	MissingNode(usize),
+	/// The key is in the trie but the proof was expected to show that it is not.
+	KeyPresent(usize),
+	/// The proof has nodes after the node where verification ended, starting at this index.
+	UnusedNodes(usize),
+}
+
+/// Whether a proof may have nodes after the node where verification ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofMode {
+	/// Reject proofs with unused trailing nodes, so that a key and its value have a single canonical
+	/// proof, which can be hashed or deduplicated.
+	Strict,
+	/// Ignore unused trailing nodes. Only `read_proof` takes a mode, as the verifiers are always strict.
+	Lenient,
+}
+
+impl ProofError {
+	fn from_node_error(error: NodeError, proof_index: usize) -> Self {
+		match error {
+			NodeError::InvalidRlp => ProofError::InvalidRlp(proof_index),
+			NodeError::InvalidPrefix => ProofError::InvalidPrefix(proof_index),
+		}
+	}
+}
+
+/// Verify that `key` maps to `expected_value` in the trie with root `expected_root`. For Ethereum's
+/// secure tries (state and storage tries) `key` is the keccak hash of the account address or storage slot.
+/// The stored value is RLP decoded before comparing it to `expected_value`, as storage trie values are the
+/// RLP encoding of the storage slot contents. An empty `expected_value` proves that the key is not in the trie.
+/// Use `verify_raw_value` for tries whose values are not RLP byte strings.
+///
+/// * `proof` - RLP encoded nodes on the path from the root to the key, starting with the root node. Nodes after
+///   the node holding the key, or showing its absence, are rejected (see `ProofMode::Strict`).
+///
+/// Never panics: any malformed proof is rejected with a `ProofError`. The work done grows with the number and
+/// length of the proof nodes, so callers verifying untrusted proofs on-chain should bound them first.
+pub fn verify_merkle_proof(
+	expected_root: &[u8],
+	proof: &[Vec<u8>],
+	key: &[u8],
+	expected_value: &[u8]) -> Result<(), ProofError>
+{
+	let (proof_index, value) = lookup(expected_root, proof, key, ProofMode::Strict)?;
+	check_value(proof_index, value, expected_value)
+}
+
+/// Verify that each `(key, expected_value)` pair of `items` is in the trie with root `expected_root`, as
+/// `verify_merkle_proof` does, with a single set of nodes shared by the proofs of all keys.
+///
+/// * `nodes` - RLP encoded nodes on the paths from the root to the keys, in any order. Nodes on the paths of
+///   several keys are given once, and are looked up by their hash. Every node must be on the path of a key.
+///
+/// Node indices in errors refer to `nodes`. A node missing from the set, including the root node, is reported
+/// as `MissingNode(nodes.len())`.
+pub fn verify_multi_proof(
+	expected_root: &[u8],
+	nodes: &[Vec<u8>],
+	items: &[(Vec<u8>, Vec<u8>)]) -> Result<(), ProofError>
+{
+	// Indices of the nodes sorted by node hash, to resolve references with a binary search.
+	let mut hashes: Vec<([u8; 32], usize)> = nodes.iter().enumerate().map(|(index, node)| (keccak(node), index)).collect();
+	hashes.sort_unstable();
+	let mut used = Vec::new();
+	used.resize(nodes.len(), false);
+
+	for (key, expected_value) in items {
+		let (node_index, value) = walk(expected_root, key, |expected_hash| {
+			let position = hashes.binary_search_by(|(hash, _)| hash[..].cmp(expected_hash))
+				.map_err(|_| ProofError::MissingNode(nodes.len()))?;
+			let index = hashes[position].1;
+			used[index] = true;
+			Ok((index, &nodes[index][..]))
+		})?;
+		check_value(node_index, value, expected_value)?;
+	}
+	// Unused nodes include copies of a node, of which only one is looked up.
+	match used.iter().position(|used| !used) {
+		Some(index) => Err(ProofError::UnusedNodes(index)),
+		None => Ok(()),
+	}
+}
+
+/// Verify that `key` maps to `expected_value` in the trie with root `expected_root`, comparing the value as
+/// stored in the trie. Unlike `verify_merkle_proof`, which RLP decodes stored values as byte strings, this
+/// verifies values that are RLP lists or other encodings, like those of the transaction and receipt tries.
+/// These tries are keyed by the RLP encoded transaction index, and their values are the RLP encoded legacy
+/// transactions and receipts, or the EIP-2718 envelopes of typed ones. An empty `expected_value` proves that
+/// the key is not in the trie.
+///
+/// * `proof` - RLP encoded nodes on the path from the root to the key, starting with the root node. Nodes after
+///   the node holding the key, or showing its absence, are rejected (see `ProofMode::Strict`).
+pub fn verify_raw_value(
+	expected_root: &[u8],
+	proof: &[Vec<u8>],
+	key: &[u8],
+	expected_value: &[u8]) -> Result<(), ProofError>
+{
+	let (proof_index, value) = lookup(expected_root, proof, key, ProofMode::Strict)?;
+	check_raw_value(proof_index, value, expected_value)
+}
+
+// Compare the value found by a lookup, as stored in the trie, to the RLP decoded `expected_value`.
+fn check_value(proof_index: usize, value: Option<&[u8]>, expected_value: &[u8]) -> Result<(), ProofError> {
+	let value = match value {
+		Some(value) => Some(Rlp::new(value).data().map_err(|_| ProofError::InvalidRlp(proof_index))?),
+		None => None,
+	};
+	check_raw_value(proof_index, value, expected_value)
+}
+
+// Compare the value found by a lookup to `expected_value`.
+fn check_raw_value(proof_index: usize, value: Option<&[u8]>, expected_value: &[u8]) -> Result<(), ProofError> {
+	match value {
+		Some(value) if value == expected_value => Ok(()),
+		Some(_) => Err(ProofError::ValueMismatch(proof_index)),
+		// If expected value is empty, that means we have proved the key does not exist in the trie.
+		// Otherwise, the expected value was not found for a key, meaning the proof is invalid.
+		None if expected_value.is_empty() => Ok(()),
+		None => Err(ProofError::KeyMismatch(proof_index)),
+	}
+}
+
+/// Verify that `key` is not in the trie with root `expected_root`, failing with `KeyPresent` if the proof
+/// holds a value for it. As in every verifier, the proof must end at the node showing that the key is absent,
+/// which is one of:
+///
+/// * a branch node without a child for the next key nibble, or without a value if the key ends there;
+/// * a leaf node whose path differs from the remaining key nibbles;
+/// * an extension node whose path diverges from the remaining key nibbles.
+pub fn verify_absence(expected_root: &[u8], proof: &[Vec<u8>], key: &[u8]) -> Result<(), ProofError> {
+	match lookup(expected_root, proof, key, ProofMode::Strict)? {
+		(proof_index, Some(_)) => Err(ProofError::KeyPresent(proof_index)),
+		(_, None) => Ok(()),
+	}
+}
+
+/// Verify that the storage slot with hashed key `key` holds `expected_value` in the storage trie with root
+/// `storage_root`. A zero `expected_value` proves that the slot is not in the trie, as the EVM does not
+/// store zero values.
+pub fn verify_storage_value(
+	storage_root: &[u8],
+	proof: &[Vec<u8>],
+	key: &[u8],
+	expected_value: U256) -> Result<(), ProofError>
+{
+	match storage_value(storage_root, proof, key)? {
+		(proof_index, Some(value)) if value != expected_value => Err(ProofError::ValueMismatch(proof_index)),
+		(proof_index, None) if !expected_value.is_zero() => Err(ProofError::KeyMismatch(proof_index)),
+		_ => Ok(()),
+	}
+}
+
+/// Read the value of the storage slot with hashed key `key` from a proof against the storage trie with
+/// root `storage_root`. Returns zero if the proof shows that the slot is not in the trie.
+pub fn read_storage_value(
+	storage_root: &[u8],
+	proof: &[Vec<u8>],
+	key: &[u8]) -> Result<U256, ProofError>
+{
+	storage_value(storage_root, proof, key).map(|(_, value)| value.unwrap_or_default())
+}
+
+// Storage trie values are the RLP encoded big endian slot contents without leading zero bytes.
+fn storage_value(
+	storage_root: &[u8],
+	proof: &[Vec<u8>],
+	key: &[u8]) -> Result<(usize, Option<U256>), ProofError>
+{
+	match lookup(storage_root, proof, key, ProofMode::Strict)? {
+		(proof_index, Some(value)) => {
+			let value = Rlp::new(value).data().map_err(|_| ProofError::InvalidRlp(proof_index))?;
+			if value.len() > 32 {
+				return Err(ProofError::InvalidRlp(proof_index));
+			}
+			Ok((proof_index, Some(U256::from_big_endian(value))))
+		},
+		(proof_index, None) => Ok((proof_index, None)),
+	}
+}
+
+/// Read the value stored at `key` in the trie with root `expected_root` from `proof`.
+/// Returns `None` if the proof shows that the key is not in the trie.
+///
+/// The value is returned as stored in the trie, e.g. the RLP encoded account for the state trie.
+/// `mode` sets whether unused nodes at the end of the proof are rejected.
+pub fn read_proof<'a>(
+	expected_root: &'a [u8],
+	proof: &'a [Vec<u8>],
+	key: &[u8],
+	mode: ProofMode) -> Result<Option<&'a [u8]>, ProofError>
+{
+	lookup(expected_root, proof, key, mode).map(|(_, value)| value)
+}
+
+// Walks the proof, and in strict mode rejects nodes after the node where the walk ended.
+pub(crate) fn lookup<'a>(
+	expected_root: &'a [u8],
+	proof: &'a [Vec<u8>],
+	key: &[u8],
+	mode: ProofMode) -> Result<(usize, Option<&'a [u8]>), ProofError>
+{
+	let mut next_index = 0;
+	let (proof_index, value) = walk(expected_root, key, |expected_hash| {
+		// The last node referenced another node that is not part of the proof.
+		let rlp_node = proof.get(next_index).ok_or(ProofError::MissingNode(next_index))?;
+		if keccak(rlp_node)[..] != *expected_hash {
+			return Err(ProofError::HashMismatch(next_index));
+		}
+		next_index += 1;
+		Ok((next_index - 1, &rlp_node[..]))
+	})?;
+	if mode == ProofMode::Strict && proof_index + 1 < proof.len() {
+		return Err(ProofError::UnusedNodes(proof_index + 1));
+	}
+	Ok((proof_index, value))
+}
+
+// Walks the trie iteratively over borrowed nodes along the key nibbles. `hashed_node` returns the index and
+// the RLP encoding of the node with the given hash, or the reason it cannot. Returns the index of the node
+// where the walk ended, and the value stored at the key, if any. Nodes embedded in their parent are not
+// looked up, and are attributed to the node holding them.
+fn walk<'a, F>(
+	expected_root: &'a [u8],
+	key: &[u8],
+	mut hashed_node: F) -> Result<(usize, Option<&'a [u8]>), ProofError>
+where
+	F: FnMut(&[u8]) -> Result<(usize, &'a [u8]), ProofError>,
+{
+	let mut key = NibbleSlice::new(key);
+	// Hash of the next node, as referenced by the root or the previous node.
+	let mut expected_hash = expected_root;
+	// The next node, if it is embedded in the previous node.
+	let mut embedded = None;
+	let mut proof_index = 0;
+
+	loop {
+		let rlp_node = match embedded.take() {
+			Some(rlp_node) => rlp_node,
+			None => {
+				let (index, rlp_node) = hashed_node(expected_hash)?;
+				proof_index = index;
+				rlp_node
+			},
+		};
+
+		let child = match Node::decode(rlp_node).map_err(|error| ProofError::from_node_error(error, proof_index))? {
+			Node::Branch { children, value } => {
+				if key.is_empty() {
+					// The key ends at this node, so its value is in the value slot, like the value of a leaf node.
+					// Only tries with keys of different lengths, where a key can be a prefix of another, store
+					// values in branch nodes.
+					return Ok((proof_index, Some(value).filter(|value| !value.is_empty())));
+				}
+				// The nibble at the start of the key is the index of the child node.
+				let child = children[key.at(0) as usize];
+				if child.is_empty() {
+					// The key is not in the trie.
+					return Ok((proof_index, None));
+				}
+				key = key.mid(1);
+				child
+			},
+			Node::Extension { path, child } => {
+				if !key.starts_with(&path) {
+					// The key diverges from all keys below the extension node, so it is not in the trie.
+					return Ok((proof_index, None));
+				}
+				key = key.mid(path.len());
+				child
+			},
+			Node::Leaf { path, value } => {
+				// The leaf node holds the key if its path is equal to the remaining key nibbles.
+				return Ok((proof_index, Some(value).filter(|_| path == key)));
+			},
+		};
+		// Hash references are 32 bytes long, embedded nodes are shorter.
+		if child.len() == 32 {
+			expected_hash = child;
+		} else {
+			embedded = Some(child);
+		}
+	}
+}
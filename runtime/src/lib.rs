@@ -7,9 +7,9 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160, H256, U256};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
+	ApplyExtrinsicResult, RuntimeString, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
@@ -267,9 +267,64 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	/// The COMP token contract, whose balances are used for governance.
+	pub const TokenContract: H160 = H160([
+		0xc0, 0x0e, 0x94, 0xcb, 0x66, 0x2c, 0x35, 0x20, 0x28, 0x2e,
+		0x6f, 0x57, 0x17, 0x21, 0x40, 0x04, 0xa7, 0xf2, 0x68, 0x88,
+	]);
+	/// Slot of the `balances` mapping in the COMP token contract.
+	pub const BalanceSlot: u64 = 1;
+	/// About 3 days of Ethereum blocks, if a storage root is stored every 10 blocks.
+	pub const MaxStoredRoots: u32 = 2_000;
+	/// Each removed storage root, vote or attestation costs about a write, so pruning takes at most about 25ms
+	/// of a block.
+	pub const MaxRemovals: u32 = 200;
+	/// Proofs of keys in tries with billions of entries are about 10 nodes deep.
+	pub const MaxProofNodes: u32 = 16;
+	/// Branch nodes referencing all 16 children by hash, the longest nodes of state and storage tries.
+	pub const MaxNodeLength: u32 = 532;
+	/// Storage slots are 32 bytes.
+	pub const MaxKeyLength: u32 = 32;
+	pub const MaxProofKeys: u32 = 32;
+	pub const MaxRelayers: u32 = 16;
+	/// Reserved from proposers until voting ends, so that flooding the chain with proposals is costly.
+	pub const ProposalDeposit: Balance = 1_000_000_000_000_000;
+	/// Together with `MaxProposalWeight`, proposals ending at a block use at most 40% of its weight.
+	pub const MaxProposals: u32 = 4;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+	/// Large enough for any call but runtime upgrades.
+	pub const MaxProposalLength: u32 = 16 * 1024;
+	pub const VotingPeriod: BlockNumber = 3 * DAYS;
+	/// 400,000 COMP, as in Compound governance.
+	pub Quorum: U256 = U256::exp10(18) * 400_000u64;
+	pub const Threshold: Perbill = Perbill::from_percent(50);
+}
+
 /// Configure the pallet-gov in pallets/gov.
 impl pallet_gov::Config for Runtime {
 	type Event = Event;
+	type TokenContract = TokenContract;
+	type BalanceSlot = BalanceSlot;
+	type MaxStoredRoots = MaxStoredRoots;
+	type MaxRemovals = MaxRemovals;
+	type MaxProofNodes = MaxProofNodes;
+	type MaxNodeLength = MaxNodeLength;
+	type MaxKeyLength = MaxKeyLength;
+	type MaxProofKeys = MaxProofKeys;
+	type MaxRelayers = MaxRelayers;
+	type Proposal = Call;
+	type Currency = Balances;
+	type ProposalDeposit = ProposalDeposit;
+	type MaxProposals = MaxProposals;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxProposalLength = MaxProposalLength;
+	type VotingPeriod = VotingPeriod;
+	type Quorum = Quorum;
+	type Threshold = Threshold;
+	// Storage roots and block headers are submitted through sudo.
+	type SubmitOrigin = frame_system::EnsureRoot<AccountId>;
+	type WeightInfo = pallet_gov::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -458,6 +513,37 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_gov_rpc_runtime_api::GovApi<Block, BlockNumber> for Runtime {
+		fn verify_storage_proof(
+			eth_block_number: BlockNumber,
+			proof: Vec<Vec<u8>>,
+			key: Vec<u8>,
+			value: U256,
+		) -> pallet_gov_rpc_runtime_api::ProofResult {
+			GovModule::verify_storage_proof(eth_block_number, &proof, &key, value)
+				.map_err(|error| RuntimeString::Borrowed(error.into()))
+		}
+		fn verify_storage_absence(
+			eth_block_number: BlockNumber,
+			proof: Vec<Vec<u8>>,
+			key: Vec<u8>,
+		) -> pallet_gov_rpc_runtime_api::ProofResult {
+			GovModule::verify_storage_absence(eth_block_number, &proof, &key)
+				.map_err(|error| RuntimeString::Borrowed(error.into()))
+		}
+		fn verify_storage_multi_proof(
+			eth_block_number: BlockNumber,
+			nodes: Vec<Vec<u8>>,
+			items: Vec<(Vec<u8>, U256)>,
+		) -> pallet_gov_rpc_runtime_api::ProofResult {
+			GovModule::verify_storage_multi_proof(eth_block_number, &nodes, &items)
+				.map_err(|error| RuntimeString::Borrowed(error.into()))
+		}
+		fn storage_root_at(eth_block_number: BlockNumber) -> Option<H256> {
+			GovModule::storage_root(eth_block_number)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -492,6 +578,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_gov, GovModule);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)